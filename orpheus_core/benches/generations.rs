@@ -0,0 +1,27 @@
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use orpheus_core::{composer::{config::Config, genetic_composer::GeneticComposer}, melodies};
+
+// Generations per second on Amazing Grace with the default config, which the phone needs to keep under a second per run.
+fn bench_generations(c: &mut Criterion) {
+
+    let melody = melodies::amazing_grace();
+    let config = Config { seed: Some(0), ..Config::default() };
+
+    let mut group = c.benchmark_group("amazing_grace");
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function("generation", |b| {
+        let mut genetic_composer = GeneticComposer::new(&melody, &config).unwrap();
+        b.iter(|| genetic_composer.step());
+    });
+
+    group.bench_function("initial_pool", |b| {
+        b.iter(|| GeneticComposer::new(&melody, &config).unwrap());
+    });
+
+    group.finish();
+
+}
+
+criterion_group!(benches, bench_generations);
+criterion_main!(benches);
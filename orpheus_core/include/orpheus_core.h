@@ -0,0 +1,93 @@
+#ifndef ORPHEUS_CORE_H
+#define ORPHEUS_CORE_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Reasons a compose run stopped, as returned in OrpheusComposition.stop_reason.
+#define ORPHEUS_STOP_GENERATION_LIMIT 0
+#define ORPHEUS_STOP_STAGNATION 1
+#define ORPHEUS_STOP_TARGET_FITNESS 2
+#define ORPHEUS_STOP_TIME_BUDGET 3
+#define ORPHEUS_STOP_LOW_DIVERSITY 4
+#define ORPHEUS_STOP_OBSERVER_STOPPED 5
+#define ORPHEUS_STOP_CANCELLED 6
+
+typedef struct OrpheusCancellationToken OrpheusCancellationToken;
+
+typedef struct OrpheusNote {
+    uint16_t midi_note;
+    uint16_t duration; // Semiquavers.
+} OrpheusNote;
+
+typedef struct OrpheusChord {
+    uint16_t root_note;
+    uint16_t chord_type; // 0 for major, 1 for minor.
+    uint16_t duration;   // Semiquavers.
+} OrpheusChord;
+
+typedef struct OrpheusProgress {
+    size_t generation;
+    size_t max_generations;
+    double best_fitness; // Fitness is a weighted sum of terms normalised to 0-1.
+    double mean_fitness;
+    double worst_fitness;
+    double diversity; // NaN when the run doesn't measure diversity.
+    double mutation_scale; // Multiplier the mutation schedule applies to the mutation rates.
+    const OrpheusChord *best_chords; // Only valid for the duration of the callback.
+    size_t best_chord_count;
+} OrpheusProgress;
+
+typedef struct OrpheusCandidate {
+    OrpheusChord *chords;
+    size_t chord_count;
+    double fitness;
+    double distance_from_best;
+} OrpheusCandidate;
+
+typedef struct OrpheusComposition {
+    OrpheusCandidate *candidates;
+    size_t candidate_count;
+    uint32_t stop_reason;
+    size_t generations;
+} OrpheusComposition;
+
+// Called every generation on the thread running orpheus_compose. Return false to stop the run.
+typedef bool (*OrpheusProgressCallback)(const OrpheusProgress *progress, void *user_data);
+
+OrpheusCancellationToken *orpheus_cancellation_token_new(void);
+// Safe to call from any thread while orpheus_compose is running.
+void orpheus_cancellation_token_cancel(const OrpheusCancellationToken *token);
+void orpheus_cancellation_token_free(OrpheusCancellationToken *token);
+
+// Blocks until the run stops. callback and cancellation_token may be null.
+// Returns null if the melody is empty, a note has no duration or the melody lasts longer than 65535 semiquavers.
+// Free the result with orpheus_composition_free.
+OrpheusComposition *orpheus_compose(const OrpheusNote *melody,
+                                    size_t melody_length,
+                                    OrpheusProgressCallback callback,
+                                    void *user_data,
+                                    const OrpheusCancellationToken *cancellation_token);
+void orpheus_composition_free(OrpheusComposition *composition);
+
+// JSON explanation of a progression's fitness against a melody: every fitness term, and for every melody note
+// whether it is a chord tone, scale tone or clash in the key detected from the melody. Returns null if either
+// is empty, a note has no duration, the melody lasts longer than 65535 semiquavers, a chord is invalid
+// or the progression doesn't last as long as the melody.
+// Free the result with orpheus_string_free.
+char *orpheus_fitness_breakdown_json(const OrpheusNote *melody,
+                                     size_t melody_length,
+                                     const OrpheusChord *chords,
+                                     size_t chord_count);
+void orpheus_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
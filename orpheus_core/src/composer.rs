@@ -1,29 +1,122 @@
+pub mod adaptation;
 pub mod chromosome;
+pub mod config;
+pub mod constraints;
+pub mod crossover;
+pub mod genetic_composer;
+pub mod harmonizer;
+pub mod islands;
+pub mod niching;
+pub mod pareto;
+pub mod progress;
+pub mod ratings;
+pub mod results;
+pub mod seeding;
+pub mod selection;
+pub mod stopping;
+
+use rand::{Rng, seq::IndexedRandom};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::cmp::Ordering;
+
+use crate::composer::{
+    chromosome::{Chromosome, ChromosomeError, fitness::FitnessWeights},
+    config::{Config, ConfigError, MutationConfig},
+    crossover::Crossover,
+    genetic_composer::GeneticComposer,
+    pareto::ParetoCandidate,
+    progress::{CancellationToken, Control, GenerationStats},
+    results::Candidate,
+    stopping::StopReason,
+};
+use crate::theory::Key;
+
+// Time signature is fixed at 4/4, measured in semiquavers.
+pub const BEAT_DURATION: u16 = 4;
+pub const BAR_DURATION: u16 = 16;
+
+#[derive(Debug, Clone)]
+pub struct Composition {
+    pub candidates: Vec<Candidate>,
+    // Progressions in the final population that no other beats on every objective.
+    pub pareto_front: Vec<ParetoCandidate>,
+    pub stop_reason: StopReason,
+    // Number of generations evolved before stopping.
+    pub generations: usize,
+    // Key the melody was judged in, for spelling chord symbols.
+    pub key: Key,
+}
+
+// Evolves chord progressions for the melody until a stopping criterion is met,
+// returning a diverse selection of the best progressions found. Fails if the config is invalid.
+pub fn compose(melody: &[(u16, u16)], config: &Config) -> Result<Composition, ConfigError> {
+    compose_with_observer(melody, config, |_| Control::Continue, &CancellationToken::new())
+}
+
+// As compose, calling the observer with the pool's stats every generation.
+// The run stops early if the observer returns Control::Stop or the token is cancelled.
+pub fn compose_with_observer<F>(melody: &[(u16, u16)], config: &Config, observer: F, cancellation_token: &CancellationToken) -> Result<Composition, ConfigError>
+where
+    F: FnMut(&GenerationStats) -> Control,
+{
 
-use rand::{rngs::ThreadRng, seq::IndexedRandom};
+    let mut genetic_composer = GeneticComposer::new(melody, config)?;
+    let stop_reason = genetic_composer.run_until(&config.stopping, observer, cancellation_token);
 
-use crate::composer::chromosome::Chromosome;
+    Ok(Composition {
+        candidates: genetic_composer.candidates(),
+        pareto_front: genetic_composer.pareto_front(),
+        stop_reason,
+        generations: genetic_composer.generation(),
+        key: genetic_composer.key(),
+    })
 
-pub fn calculate_melody_duration(melody: &Vec::<(u16, u16)>) -> u16 {
+}
+
+pub fn calculate_melody_duration(melody: &[(u16, u16)]) -> u16 {
     melody.iter().map(|note| note.1).sum()
 }
 
-pub fn calculate_pool_fitness(chromosome_pool: &mut Vec::<Chromosome>, melody: &Vec::<(u16, u16)>) {
+#[cfg(not(feature = "parallel"))]
+pub fn calculate_pool_fitness(chromosome_pool: &mut [Chromosome], melody: &[(u16, u16)], key: &Key, weights: &FitnessWeights) {
 
     for chromosome in chromosome_pool {
-        chromosome.calculate_fitness(melody);
+        chromosome.calculate_fitness(melody, key, weights);
     }
 
 }
 
-pub fn select_top_n_pool(chromosome_pool: &Vec::<Chromosome>, quantity: usize) -> Vec::<Chromosome> {
+#[cfg(feature = "parallel")]
+pub fn calculate_pool_fitness(chromosome_pool: &mut [Chromosome], melody: &[(u16, u16)], key: &Key, weights: &FitnessWeights) {
+
+    chromosome_pool.par_iter_mut().for_each(|chromosome| chromosome.calculate_fitness(melody, key, weights));
+
+}
+
+// Sorts the pool from fittest to least fit, keeping the order of equally fit chromosomes.
+pub fn sort_pool_by_fitness(chromosome_pool: &mut [Chromosome]) {
+    chromosome_pool.sort_by(compare_by_fitness_descending);
+}
+
+// Fittest first, for sorting.
+pub fn compare_by_fitness_descending(chromosome_one: &Chromosome, chromosome_two: &Chromosome) -> Ordering {
+    chromosome_two.get_fitness().total_cmp(&chromosome_one.get_fitness())
+}
+
+pub fn select_top_n_pool(chromosome_pool: &[Chromosome], quantity: usize) -> Vec::<Chromosome> {
 
     if quantity >= chromosome_pool.len() {
-        return chromosome_pool.clone();
+        return chromosome_pool.to_vec();
+    }
+
+    // A pool already sorted by sort_pool_by_fitness doesn't need cloning and sorting again.
+    if chromosome_pool.is_sorted_by(|chromosome_one, chromosome_two| compare_by_fitness_descending(chromosome_one, chromosome_two).is_le()) {
+        return chromosome_pool[0..quantity].iter().rev().cloned().collect();
     }
 
-    let mut sorted_chromosome_pool: Vec::<Chromosome> = chromosome_pool.clone();
-    sorted_chromosome_pool.sort_by(|c1, c2| c2.get_fitness().cmp(&c1.get_fitness()));
+    let mut sorted_chromosome_pool: Vec::<Chromosome> = chromosome_pool.to_vec();
+    sort_pool_by_fitness(&mut sorted_chromosome_pool);
 
     let mut top_n = sorted_chromosome_pool[0..quantity].to_vec();
     
@@ -33,15 +126,13 @@ pub fn select_top_n_pool(chromosome_pool: &Vec::<Chromosome>, quantity: usize) -
 
 }
 
-pub fn generate_chromosome_pool(initial_size: usize, melody_duration: u16) -> Vec::<Chromosome> {
-
-    let mut rng = rand::rng();
+pub fn generate_chromosome_pool<R: Rng + ?Sized>(rng: &mut R, initial_size: usize, melody_duration: u16) -> Vec::<Chromosome> {
 
     let mut chromosome_pool = Vec::<Chromosome>::new();
 
     for _ in 0..initial_size {
 
-        let new_chromosome = chromosome::generate_random_chromosome(&mut rng, melody_duration);
+        let new_chromosome = chromosome::generate_random_chromosome(rng, melody_duration);
 
         chromosome_pool.push(new_chromosome);
 
@@ -51,29 +142,36 @@ pub fn generate_chromosome_pool(initial_size: usize, melody_duration: u16) -> Ve
 
 }
 
-pub fn mutate_chromosome_pool(chromosome_pool: &mut Vec::<Chromosome>, mutation_rate: f64) {
+// Tops the pool back up to `size` with random chromosomes.
+pub fn refill_chromosome_pool<R: Rng + ?Sized>(rng: &mut R, chromosome_pool: &mut Vec::<Chromosome>, size: usize, melody_duration: u16) {
+
+    while chromosome_pool.len() < size {
+        chromosome_pool.push(chromosome::generate_random_chromosome(rng, melody_duration));
+    }
+
+}
 
-    let mut rng = rand::rng();
+pub fn mutate_chromosome_pool<R: Rng + ?Sized>(rng: &mut R, chromosome_pool: &mut [Chromosome], mutation_config: &MutationConfig) -> Result<(), ChromosomeError> {
 
     for chromosome in chromosome_pool {
-        chromosome.mutate(mutation_rate, &mut rng);
+        chromosome.mutate(mutation_config, rng)?;
     }
 
-}
+    Ok(())
 
-pub fn crossover_selection(selection: &Vec::<Chromosome>, target: usize, crossover_point: u16) -> Vec::<Chromosome> {
+}
 
-    let mut rng = rand::rng();
+pub fn crossover_selection<R: Rng + ?Sized>(rng: &mut R, selection: &[Chromosome], target: usize, crossover: Crossover) -> Vec::<Chromosome> {
 
     let mut offspring = Vec::<Chromosome>::new();
 
     while offspring.len() < target {
 
-        let (parent_one_index, parent_two_index) = choose_two_parent_indices(&mut rng, selection);
+        let (parent_one_index, parent_two_index) = choose_two_parent_indices(rng, selection);
 
         let parent_one = &selection[parent_one_index];
         let parent_two = &selection[parent_two_index];
-        let (child_one, child_two) = crossover_parents(parent_one, parent_two, crossover_point);
+        let (child_one, child_two) = crossover::crossover_parents(parent_one, parent_two, crossover, rng);
 
         offspring.extend([child_one, child_two]);
 
@@ -83,33 +181,21 @@ pub fn crossover_selection(selection: &Vec::<Chromosome>, target: usize, crossov
 
 }
 
-fn crossover_parents(parent_one: &Chromosome, parent_two: &Chromosome, crossover_point: u16) -> (Chromosome, Chromosome) {
-    
-    let (parent_one_first_segment, parent_one_second_segment) = parent_one.split_at_time(crossover_point);
-    let (parent_two_first_segment, parent_two_second_segment) = parent_two.split_at_time(crossover_point);
-
-    let mut child_one_genes = parent_one_first_segment;
-    child_one_genes.extend(parent_two_second_segment);
-    let child_one = chromosome::generate_chromosome(child_one_genes);
-
-    let mut child_two_genes = parent_two_first_segment;
-    child_two_genes.extend(parent_one_second_segment);
-    let child_two = chromosome::generate_chromosome(child_two_genes);
-
-    (child_one, child_two)
-
-}
+// A lone parent is crossed with itself.
+fn choose_two_parent_indices<R: Rng + ?Sized>(rng: &mut R, parent_pool: &[Chromosome]) -> (usize, usize) {
+    if parent_pool.len() < 2 {
+        return (0, 0);
+    }
 
-fn choose_two_parent_indices(rng: &mut ThreadRng, parent_pool: &Vec::<Chromosome>) -> (usize, usize) {
     let indices: Vec<usize> = (0..parent_pool.len()).collect();
     let parent_indices: Vec<usize> = indices.choose_multiple(rng, 2).copied().collect();
     (parent_indices[0], parent_indices[1])
 }
 
-pub fn print_chromosome_pool(chromosome_pool: &Vec::<Chromosome>, melody_duration: u16) {
+pub fn print_chromosome_pool(chromosome_pool: &[Chromosome], melody_duration: u16, key: &Key) {
 
     for chromosome in chromosome_pool {
-        chromosome.print();
+        chromosome.print(key);
         chromosome.validate(melody_duration);
     }
 
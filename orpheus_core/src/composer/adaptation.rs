@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use crate::composer::config::MutationConfig;
+
+// How the configured mutation rates change over a run.
+// Every schedule produces a scale that multiplies all of the rates in the MutationConfig.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum MutationSchedule {
+    // The configured rates every generation.
+    #[default]
+    Constant,
+    // Rates multiplied by `decay` every generation, so early generations explore and later ones refine,
+    // never dropping below `minimum_scale` of the configured rates.
+    ExponentialDecay { decay: f64, minimum_scale: f64 },
+    // Rates raised towards `boost` times the configured rates as pool diversity drops below `diversity_threshold`.
+    DiversityTriggered { diversity_threshold: f64, boost: f64 },
+}
+
+impl MutationSchedule {
+
+    // Scale to apply to the mutation rates when evolving from the given generation.
+    pub fn scale(&self, generation: usize, diversity: f64) -> f64 {
+        match *self {
+            MutationSchedule::Constant => 1.0,
+            MutationSchedule::ExponentialDecay { decay, minimum_scale } => {
+                decay.powi(generation.min(i32::MAX as usize) as i32).max(minimum_scale)
+            },
+            MutationSchedule::DiversityTriggered { diversity_threshold, boost } => {
+                if diversity >= diversity_threshold || diversity_threshold <= 0.0 {
+                    1.0
+                } else {
+                    1.0 + (boost - 1.0) * (1.0 - diversity / diversity_threshold)
+                }
+            },
+        }
+    }
+}
+
+impl MutationConfig {
+
+    // Every rate multiplied by `scale`, capped at certainty.
+    pub fn scaled(&self, scale: f64) -> MutationConfig {
+        let scale_rate = |rate: f64| (rate * scale).clamp(0.0, 1.0);
+
+        MutationConfig {
+            chord_rate: scale_rate(self.chord_rate),
+            duration_rate: scale_rate(self.duration_rate),
+            split_rate: scale_rate(self.split_rate),
+            merge_rate: scale_rate(self.merge_rate),
+            shift_boundary_rate: scale_rate(self.shift_boundary_rate),
+            transpose_rate: scale_rate(self.transpose_rate),
+            substitute_rate: scale_rate(self.substitute_rate),
+        }
+    }
+}
@@ -1,83 +1,70 @@
+pub mod fitness;
 pub mod gene;
-mod precomputed_chord_notes;
+mod mutation;
+pub mod precomputed_chord_notes;
 
-use rand::rngs::ThreadRng;
-use std::{collections::HashMap, hash::Hash};
+use rand::Rng;
+use std::{collections::HashMap, fmt, hash::{Hash, Hasher}};
 
-use crate::composer::chromosome::gene::{generate_gene, Gene, MAX_DURATION};
+use crate::{composer::chromosome::{fitness::{FitnessWeights, NoteFit}, gene::Gene}, theory::{Chord, Key, PitchClass, spelling::ChordSymbolStyle}};
 
-// Fitness calculation constants
+// Fitness calculation constants, relative to each other before being normalised to 0-1.
 const CHORD_CONTAINS_MELODY_NOTE_REWARD: i32 = 4;
 const CHORD_SCALE_CONTAINS_MELODY_NOTE_REWARD: i32 = 1;
 const CHORD_SCALE_DOESNT_CONTAIN_MELODY_NOTE_PENALTY: i32 = 1;
 
-#[derive(Clone)]
-pub struct Chromosome(Vec<Gene>, i32);
-
-impl Chromosome {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChromosomeError {
+    DurationMismatch { expected: u16, actual: u16 },
+}
 
-    fn add_gene(&mut self, gene: Gene) {
-        self.0.push(gene);
+impl fmt::Display for ChromosomeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChromosomeError::DurationMismatch { expected, actual } => write!(f, "chromosome lasts {actual} semiquavers instead of {expected}"),
+        }
     }
+}
 
-    pub fn calculate_fitness(&mut self, melody: &Vec::<(u16, u16)>) {
+impl std::error::Error for ChromosomeError {}
 
-        let mut fitness: i32 = 0;
-        
-        fitness += self.calculate_chord_melody_fitness(melody);
-        fitness += self.calculate_chord_entropy_fitness();
-        fitness += self.calculate_duration_entropy_fitness();
-
-        self.1 = fitness;
+#[derive(Debug, Clone)]
+pub struct Chromosome(Vec<Gene>, f64);
 
+// Chromosomes are the same progression if their genes match, whatever fitness they were last given.
+impl PartialEq for Chromosome {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
     }
+}
 
-    fn calculate_chord_melody_fitness(&self, melody: &Vec::<(u16, u16)>) -> i32 {
-
-        let mut fitness: i32 = 0;
-
-        // Go through every melody note.
-        // Check whether it is in the chord's note (+3),
-        // or in its scale (+1).
-        // Penalise if it is neither (-1).
-
-        let mut cumulative_melody_time = 0;
-        let mut cumulative_chord_time = 0;
-        let mut current_chord_number = 0;
-
-        for note in melody {
-
-            let pitch_class = note.0 % 12;
-
-            cumulative_melody_time += note.1;
-
-            while cumulative_chord_time < cumulative_melody_time {
-                current_chord_number += 1;
-                cumulative_chord_time += self.0[current_chord_number-1].get_duration();
-            }
+impl Eq for Chromosome {}
 
-            if cumulative_chord_time == cumulative_melody_time {
-                fitness += 2;
-            }
+impl Hash for Chromosome {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
 
-            let chord = self.0[current_chord_number-1].get_chord() as usize;
+impl Chromosome {
 
-            if precomputed_chord_notes::CHORD_NOTES[chord].contains(&pitch_class) {
-                fitness += CHORD_CONTAINS_MELODY_NOTE_REWARD;
-            }
-            else if precomputed_chord_notes::CHORD_SCALE_NOTES[chord].contains(&pitch_class) {
-                fitness += CHORD_SCALE_CONTAINS_MELODY_NOTE_REWARD;
-            }
-            else {
-                fitness -= CHORD_SCALE_DOESNT_CONTAIN_MELODY_NOTE_PENALTY;
-            }
+    fn add_gene(&mut self, gene: Gene) {
+        self.0.push(gene);
+    }
 
-        }
+    pub fn calculate_fitness(&mut self, melody: &[(u16, u16)], key: &Key, weights: &FitnessWeights) {
+        self.1 = self.fitness_breakdown(melody, key, weights).total;
+    }
 
-        fitness
+    // Weighted fitness from how well each chord fits the melody notes ending under it and how many notes end
+    // as the chord changes, the only part of the fitness that can be worked out one chord at a time.
+    pub fn calculate_chord_melody_fitness(&self, melody: &[(u16, u16)], key: &Key, weights: &FitnessWeights) -> f64 {
+        let (chord_melody, boundary_alignment) = fitness::note_terms(&self.note_fitnesses(melody, key));
+        weights.chord_melody * chord_melody + weights.boundary_alignment * boundary_alignment
     }
 
-    fn calculate_chord_entropy_fitness(&self) -> i32 {
+    // 1 for a progression that repeats some chords without being monotonous, down to 0.
+    fn calculate_chord_entropy_fitness(&self) -> f64 {
 
         let chords: Vec<u16> = self.0.iter().map(|c| c.get_chord()).collect();
         let number_of_chords = chords.len();
@@ -85,7 +72,7 @@ impl Chromosome {
         let mut chord_hash: HashMap<u16, u16> = HashMap::new();
 
         for chord in &chords {
-            match chord_hash.get(&chord) {
+            match chord_hash.get(chord) {
                 Some(&number) => {chord_hash.insert(*chord, number + 1);},
                 _ => {chord_hash.insert(*chord, 1);}
             }
@@ -97,21 +84,22 @@ impl Chromosome {
             chord_entropy -= probablility * f64::log2(probablility);
         }
 
-        if 0.8 <= chord_entropy && chord_entropy <= 1.2 {
-            return 30;
+        if (0.8..=1.2).contains(&chord_entropy) {
+            return 1.0;
         }
-        else if chord_entropy <= 1.8 || chord_entropy >= 0.6 {
-            return 20;
+        else if (0.6..=1.8).contains(&chord_entropy) {
+            return 0.75;
         }
-        else if chord_entropy <= 2.4 || chord_entropy >= 0.2 {
-            return 10;
+        else if (0.2..=2.4).contains(&chord_entropy) {
+            return 0.5;
         }
         
-        -10
+        0.0
 
     }
 
-    fn calculate_duration_entropy_fitness(&self) -> i32 {
+    // 1 for a progression with a moderate mix of chord lengths, down to 0.
+    fn calculate_duration_entropy_fitness(&self) -> f64 {
         
         let durations: Vec<u16> = self.0.iter().map(|c| c.get_duration()).collect();
         let number_of_durations = durations.len();
@@ -131,110 +119,36 @@ impl Chromosome {
             duration_entropy -= probablility * f64::log2(probablility);
         }
 
-        println!("Duration entropy: {duration_entropy}");
-
-        if 1.8 <= duration_entropy && duration_entropy <= 2.4 {
-            return 10;
+        if (1.8..=2.4).contains(&duration_entropy) {
+            return 1.0;
         }
-        else if duration_entropy <= 3.0 || duration_entropy >= 1.2 {
-            return 5;
+        else if (1.2..=3.0).contains(&duration_entropy) {
+            return 0.75;
         }   
         
-        -10
+        0.0
     }
 
-    pub fn get_fitness(&self) -> i32 {
+    pub fn get_fitness(&self) -> f64 {
         self.1
     }
 
-    pub fn mutate(&mut self, mutation_rate: f64, rng: &mut ThreadRng) {
-
-        self.mutate_chord(mutation_rate, rng);
-        self.mutate_duration(mutation_rate, rng);
-
-        if !self.is_valid_chromosome(96) {
-            println!("Invalid Chromosome!");
-        }
-
+    pub fn set_fitness(&mut self, fitness: f64) {
+        self.1 = fitness;
     }
 
-    fn mutate_chord(&mut self, mutation_rate: f64, rng: &mut ThreadRng) {
-        
-        for gene in &mut self.0 {
-
-            gene.mutate_chord(mutation_rate, rng);
-
-        }
-
+    // Symbol of every gene's chord, spelt for the key.
+    pub fn chord_symbols(&self, key: &Key, style: &ChordSymbolStyle) -> Vec<String> {
+        self.0.iter().map(|gene| gene.chord().symbol(key, style)).collect()
     }
 
-    fn mutate_duration(&mut self, mutation_rate: f64, rng: &mut ThreadRng) {
-
-        let mut counter: usize = 0;
-        while counter < self.0.len() {
-
-            let mut duration_change = self.0[counter].mutate_duration_and_return_change(mutation_rate, rng);
-
-            let mut repair_counter = counter + 1;
-
-            while duration_change != 0 && repair_counter < self.0.len() {
-
-                let repairing_gene = &mut self.0[repair_counter];
-                let repairing_gene_duration = repairing_gene.get_duration() as i16;
-
-                if repairing_gene_duration > duration_change {
-
-                    if (repairing_gene_duration - duration_change) as u16 > gene::MAX_DURATION {
-                        repairing_gene.set_duration(MAX_DURATION);
-                        duration_change += MAX_DURATION as i16 - repairing_gene_duration;
-                        repair_counter += 1;
-                    }
-                    else {
-                        repairing_gene.set_duration((repairing_gene_duration - duration_change) as u16);
-                        duration_change = 0;
-                    }
-                }
-                else {
-                    duration_change -= repairing_gene_duration;
-                    self.0.remove(repair_counter);
-                }
-            }
-
-            repair_counter = counter;
-
-            while duration_change != 0 && repair_counter > 0 {
-
-                let repairing_gene = &mut self.0[repair_counter-1];
-                let repairing_gene_duration = repairing_gene.get_duration() as i16;
-                
-                if repairing_gene_duration > duration_change {
-
-                    if (repairing_gene_duration - duration_change) as u16 > gene::MAX_DURATION {
-                        repairing_gene.set_duration(MAX_DURATION);
-                        duration_change += MAX_DURATION as i16 - repairing_gene_duration;
-                        repair_counter -= 1;
-                    }
-                    else {
-                        repairing_gene.set_duration((repairing_gene_duration - duration_change) as u16);
-                        duration_change = 0;
-                    }
-
-                }
-                else {
-                    duration_change -= repairing_gene_duration;
-                    self.0.remove(repair_counter-1);
-                    repair_counter -= 1;
-                    counter -= 1;
-                }
-            }
-            
-            if duration_change != 0 {
-                self.0[counter].set_duration(gene::MAX_DURATION);
-            }
-
-            counter += 1;
+    pub fn get_genes(&self) -> &[Gene] {
+        &self.0
+    }
 
-        }
+    // Total duration of every gene in the chromosome, in semiquavers.
+    pub fn get_duration(&self) -> u16 {
+        self.0.iter().map(|gene| gene.get_duration()).sum()
     }
 
     pub fn split_at_time(&self, time: u16) -> (Vec<Gene>, Vec<Gene>) {
@@ -247,7 +161,7 @@ impl Chromosome {
         for gene in &self.0 {
 
             if first_segment_duration >= time {
-                second_segment.push(gene.clone());
+                second_segment.push(*gene);
             }
 
             // Gene's duration takes segment over the time split, so split the gene.
@@ -255,14 +169,15 @@ impl Chromosome {
                 let first_segment_split_gene_duration = time - first_segment_duration;
                 let second_segment_split_gene_duration = gene.get_duration() - first_segment_split_gene_duration;
                 
-                first_segment.push(generate_gene(gene.get_root_note(), gene.get_chord_type(), first_segment_split_gene_duration));
-                second_segment.push(generate_gene(gene.get_root_note(), gene.get_chord_type(), second_segment_split_gene_duration));
+                // Both halves are non-empty and shorter than the original gene, so always valid durations.
+                first_segment.push(gene.with_duration(first_segment_split_gene_duration).expect("split gene duration is in range"));
+                second_segment.push(gene.with_duration(second_segment_split_gene_duration).expect("split gene duration is in range"));
             
                 first_segment_duration = time;
             }
 
             else {
-                first_segment.push(gene.clone());
+                first_segment.push(*gene);
                 first_segment_duration += gene.get_duration();
             }
 
@@ -272,9 +187,7 @@ impl Chromosome {
     }
 
     fn is_valid_chromosome(&self, melody_duration: u16) -> bool {
-        let duration: u16 = self.0.iter().map(|gene| gene.get_duration()).sum();
-
-        duration == melody_duration
+        self.get_duration() == melody_duration
     }
 
     pub fn validate(&self, melody_duration: u16) {
@@ -285,32 +198,58 @@ impl Chromosome {
         }
     }
 
-    pub fn print(&self) {
+    pub fn print(&self, key: &Key) {
         let fitness = self.1;
 
         println!("--------CHROMOSOME--------");
-        println!("FITNESS: {fitness}");
+        println!("FITNESS: {fitness:.3}");
         for gene in &self.0 {
-            gene.print();
+            gene.print(key);
         }
     }
 }
 
+// Fitness of a melody note's pitch class against the chord playing as it ends, from 0 to 1.
+pub fn melody_note_fitness(chord: Chord, pitch_class: PitchClass, key: &Key) -> f64 {
+    classify_melody_note(chord, pitch_class, key).fitness()
+}
+
+// Chord tones fit best, then notes of the scale the chord's function in the key implies.
+pub fn classify_melody_note(chord: Chord, pitch_class: PitchClass, key: &Key) -> NoteFit {
+
+    if precomputed_chord_notes::chord_notes(chord).contains(&pitch_class) {
+        NoteFit::ChordTone
+    }
+    else if precomputed_chord_notes::chord_scale_notes(chord, key).contains(&pitch_class) {
+        NoteFit::ScaleTone
+    }
+    else {
+        NoteFit::Clash
+    }
+
+}
+
 // Factory function to construct a chromosome with given genes.
 pub fn generate_chromosome(genes: Vec::<Gene>) -> Chromosome {
-    Chromosome(genes, 0)
+    Chromosome(genes, 0.0)
 }
 
 // Factory function to construct a chromosome with random genes.
-pub fn generate_random_chromosome(rng: &mut ThreadRng, melody_duration: u16) -> Chromosome {
+pub fn generate_random_chromosome<R: Rng + ?Sized>(rng: &mut R, melody_duration: u16) -> Chromosome {
 
-    let mut chromosome = Chromosome(Vec::<Gene>::new(), 0);
+    let mut chromosome = Chromosome(Vec::<Gene>::new(), 0.0);
     let mut duration: u16 = 0;
 
-    // Fill chromosome with genes until melody duration is exceeded.
+    // Fill chromosome with genes until melody duration is reached.
     while duration < melody_duration {
 
-        let gene = gene::generate_random_gene(rng);
+        let mut gene = gene::generate_random_gene(rng);
+
+        // Truncate any overflow of duration on the final gene.
+        let remaining_duration = melody_duration - duration;
+        if gene.get_duration() > remaining_duration {
+            gene = gene.with_duration(remaining_duration).expect("remaining duration is shorter than a valid gene duration");
+        }
 
         duration += gene.get_duration();
 
@@ -318,11 +257,5 @@ pub fn generate_random_chromosome(rng: &mut ThreadRng, melody_duration: u16) ->
 
     }
 
-    // Truncate any overflow of duration.
-    let duration_overflow = duration - melody_duration;
-    let number_of_genes = chromosome.0.len();
-    let final_gene_duration = chromosome.0[number_of_genes - 1].get_duration();
-    chromosome.0[number_of_genes - 1].set_duration(final_gene_duration - duration_overflow);
-
     chromosome
 }
\ No newline at end of file
@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+
+use crate::composer::chromosome::{
+    self,
+    CHORD_CONTAINS_MELODY_NOTE_REWARD,
+    CHORD_SCALE_CONTAINS_MELODY_NOTE_REWARD,
+    CHORD_SCALE_DOESNT_CONTAIN_MELODY_NOTE_PENALTY,
+    Chromosome,
+};
+use crate::theory::{Key, PitchClass};
+
+const CHORD_MELODY_WEIGHT: f64 = 0.5;
+const BOUNDARY_ALIGNMENT_WEIGHT: f64 = 0.2;
+const CHORD_ENTROPY_WEIGHT: f64 = 0.2;
+const DURATION_ENTROPY_WEIGHT: f64 = 0.1;
+const RATING_WEIGHT: f64 = 0.2;
+
+// How much each fitness term counts towards the total.
+// Every term is normalised to 0-1 (ratings to -1-1), so a weight is the most fitness its term can give
+// whatever the length of the melody.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FitnessWeights {
+    pub chord_melody: f64,
+    pub boundary_alignment: f64,
+    pub chord_entropy: f64,
+    pub duration_entropy: f64,
+    pub rating: f64,
+}
+
+impl Default for FitnessWeights {
+    fn default() -> Self {
+        FitnessWeights {
+            chord_melody: CHORD_MELODY_WEIGHT,
+            boundary_alignment: BOUNDARY_ALIGNMENT_WEIGHT,
+            chord_entropy: CHORD_ENTROPY_WEIGHT,
+            duration_entropy: DURATION_ENTROPY_WEIGHT,
+            rating: RATING_WEIGHT,
+        }
+    }
+}
+
+// How a melody note sits against the chord playing as it ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NoteFit {
+    ChordTone,
+    ScaleTone,
+    Clash,
+}
+
+impl NoteFit {
+
+    // From 0 for a clash to 1 for a chord tone.
+    pub fn fitness(&self) -> f64 {
+        let reward = match self {
+            NoteFit::ChordTone => CHORD_CONTAINS_MELODY_NOTE_REWARD,
+            NoteFit::ScaleTone => CHORD_SCALE_CONTAINS_MELODY_NOTE_REWARD,
+            NoteFit::Clash => -CHORD_SCALE_DOESNT_CONTAIN_MELODY_NOTE_PENALTY,
+        };
+
+        (reward + CHORD_SCALE_DOESNT_CONTAIN_MELODY_NOTE_PENALTY) as f64
+            / (CHORD_CONTAINS_MELODY_NOTE_REWARD + CHORD_SCALE_DOESNT_CONTAIN_MELODY_NOTE_PENALTY) as f64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteFitness {
+    // Index of the note in the melody.
+    pub note_index: usize,
+    // Semiquaver the note starts on.
+    pub start: u16,
+    pub duration: u16,
+    pub midi_note: u16,
+    // Index of the gene playing as the note ends.
+    pub gene_index: usize,
+    pub fit: NoteFit,
+    pub fitness: f64,
+    // Whether the chord changes as the note ends, counting towards boundary alignment.
+    pub ends_with_chord: bool,
+}
+
+// Every term that makes up a chromosome's fitness, each normalised before weighting,
+// and how each melody note contributed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FitnessBreakdown {
+    // Mean fitness of every melody note against its chord.
+    pub chord_melody: f64,
+    // Fraction of melody notes that end as the chord changes.
+    pub boundary_alignment: f64,
+    pub chord_entropy: f64,
+    pub duration_entropy: f64,
+    // Fitness from user ratings, which only the composer holding the ratings can fill in.
+    pub rating: f64,
+    pub weights: FitnessWeights,
+    // Weighted sum of every term.
+    pub total: f64,
+    pub notes: Vec<NoteFitness>,
+}
+
+impl FitnessBreakdown {
+
+    pub fn set_rating(&mut self, rating: f64) {
+        self.rating = rating;
+        self.total = self.weighted_total();
+    }
+
+    // Melody notes that are in neither the chord nor its scale, for highlighting.
+    pub fn clashes(&self) -> impl Iterator<Item = &NoteFitness> {
+        self.notes.iter().filter(|note| note.fit == NoteFit::Clash)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    fn weighted_total(&self) -> f64 {
+        self.weights.chord_melody * self.chord_melody
+            + self.weights.boundary_alignment * self.boundary_alignment
+            + self.weights.chord_entropy * self.chord_entropy
+            + self.weights.duration_entropy * self.duration_entropy
+            + self.weights.rating * self.rating
+    }
+}
+
+impl Chromosome {
+
+    pub fn fitness_breakdown(&self, melody: &[(u16, u16)], key: &Key, weights: &FitnessWeights) -> FitnessBreakdown {
+
+        let notes = self.note_fitnesses(melody, key);
+        let (chord_melody, boundary_alignment) = note_terms(&notes);
+
+        let mut breakdown = FitnessBreakdown {
+            chord_melody,
+            boundary_alignment,
+            chord_entropy: self.calculate_chord_entropy_fitness(),
+            duration_entropy: self.calculate_duration_entropy_fitness(),
+            rating: 0.0,
+            weights: *weights,
+            total: 0.0,
+            notes,
+        };
+
+        breakdown.total = breakdown.weighted_total();
+
+        breakdown
+
+    }
+
+    // Scores every melody note against the chord playing as it ends, in the song's key.
+    pub fn note_fitnesses(&self, melody: &[(u16, u16)], key: &Key) -> Vec<NoteFitness> {
+
+        // Without chords there is nothing to score the notes against.
+        if self.0.is_empty() {
+            return Vec::new();
+        }
+
+        let mut note_fitnesses = Vec::<NoteFitness>::with_capacity(melody.len());
+
+        let mut cumulative_melody_time = 0;
+        let mut cumulative_chord_time = 0;
+        let mut current_chord_number = 0;
+
+        for (note_index, note) in melody.iter().enumerate() {
+
+            let start = cumulative_melody_time;
+            cumulative_melody_time += note.1;
+
+            while cumulative_chord_time < cumulative_melody_time {
+                current_chord_number += 1;
+                cumulative_chord_time += self.0[current_chord_number-1].get_duration();
+            }
+
+            // A zero-length note at the very start ends before any chord has played, so it goes with the first.
+            let gene_index = current_chord_number.saturating_sub(1);
+            let fit = chromosome::classify_melody_note(self.0[gene_index].chord(), PitchClass::from_semitones(note.0), key);
+
+            note_fitnesses.push(NoteFitness {
+                note_index,
+                start,
+                duration: note.1,
+                midi_note: note.0,
+                gene_index,
+                fit,
+                fitness: fit.fitness(),
+                ends_with_chord: cumulative_chord_time == cumulative_melody_time,
+            });
+
+        }
+
+        note_fitnesses
+
+    }
+}
+
+// Chord-melody and boundary alignment terms, as means over the melody's notes.
+pub fn note_terms(notes: &[NoteFitness]) -> (f64, f64) {
+
+    if notes.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let note_count = notes.len() as f64;
+    let chord_melody = notes.iter().map(|note| note.fitness).sum::<f64>() / note_count;
+    let boundary_alignment = notes.iter().filter(|note| note.ends_with_chord).count() as f64 / note_count;
+
+    (chord_melody, boundary_alignment)
+
+}
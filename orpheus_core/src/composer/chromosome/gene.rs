@@ -1,92 +1,198 @@
-use rand::{Rng, rngs::ThreadRng};
-use num::pow;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::theory::{Chord, ChordQuality, Interval, Key, PitchClass, TheoryError, spelling::ChordSymbolStyle};
 
 // Chord Representation:
+// Typed root note, chord quality and duration.
+// A compact encoding is retained for mutation and serialisation:
 // 4 bits for root note
 // 1 bit for major or minor (maybe 2 bits to also have augmented and diminished)
-// 1 bit for seventh
+// 5 bits for duration
+// seventh ?
 // ninth ?
 // sus chords ?
-// 5 bits for duration
 
-const MAX_ROOT_NOTE: u16 = 11;
+// Bumped whenever the layout of the encoding changes, so old checkpoints are rejected.
+pub const ENCODING_VERSION: u32 = 1;
+
+pub const MAX_ROOT_NOTE: u16 = 11;
 pub const MAX_DURATION: u16 = 32;
-const MAX_CHORD_TYPE: u16 = 1;
+pub const MAX_CHORD_TYPE: u16 = 1;
 
-const ROOT_NOTE_MASK: u16 = 0b1111_0_00000;
+const ROOT_NOTE_MASK: u16 = 0b11_1100_0000;
 const ROOT_NOTE_SHIFT: u16 = 6;
-//const ROOT_NOTE_SIZE: u16 = 4;
 
-const CHORD_TYPE_MASK: u16 = 0b1_00000;
+const CHORD_TYPE_MASK: u16 = 0b10_0000;
 const CHORD_TYPE_SHIFT: u16 = 5;
-const CHORD_TYPE_SIZE: u16 = 1;
 
-const DURATION_MASK: u16 = 0b11111;
+const DURATION_MASK: u16 = 0b1_1111;
 const DURATION_SHIFT: u16 = 0;
 const DURATION_SIZE: u16 = 5;
 
-const ROOT_NOTE_LETTERS: [&str; 12] = ["C", "C♯/D♭", "D", "D♯/E♭", "E", "F", "F♯/G♭", "G", "G♯/A♭", "A", "A♯/B♭", "B"];
-const CHORD_TYPE_NAMES: [&str; 2] = ["Major", "Minor"];
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneError {
+    InvalidRootNote(u16),
+    InvalidChordType(u16),
+    InvalidDuration(u16),
+    InvalidEncoding(u16),
+}
 
-#[derive(Clone)]
-pub struct Gene(u16);
+impl fmt::Display for GeneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneError::InvalidRootNote(root_note) => write!(f, "root note {root_note} is not in 0..={MAX_ROOT_NOTE}"),
+            GeneError::InvalidChordType(chord_type) => write!(f, "chord type {chord_type} is not in 0..={MAX_CHORD_TYPE}"),
+            GeneError::InvalidDuration(duration) => write!(f, "duration {duration} is not in 1..={MAX_DURATION}"),
+            GeneError::InvalidEncoding(encoding) => write!(f, "{encoding:#b} is not a valid gene encoding"),
+        }
+    }
+}
 
-impl Gene {
+impl std::error::Error for GeneError {}
 
-    pub fn get_root_note(&self) -> u16 {
-        (self.0 & ROOT_NOTE_MASK) >> ROOT_NOTE_SHIFT
+impl From<TheoryError> for GeneError {
+    fn from(error: TheoryError) -> Self {
+        match error {
+            TheoryError::InvalidPitchClass(root_note) => GeneError::InvalidRootNote(root_note),
+            TheoryError::InvalidChordQuality(chord_type) | TheoryError::InvalidChord(chord_type) => GeneError::InvalidChordType(chord_type),
+        }
     }
+}
 
-    pub fn set_root_note(&mut self, root_note: u16) {
-        if root_note > MAX_ROOT_NOTE {
-            return;
+// Chord duration in semiquavers, 1 to MAX_DURATION.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Duration(u8);
+
+impl Duration {
+
+    pub fn new(duration: u16) -> Result<Duration, GeneError> {
+        if duration == 0 || duration > MAX_DURATION {
+            return Err(GeneError::InvalidDuration(duration));
         }
 
-        self.0 &= !ROOT_NOTE_MASK;
-        self.0 |= root_note << ROOT_NOTE_SHIFT;
+        Ok(Duration(duration as u8))
     }
 
-    pub fn get_chord_type(&self) -> u16 {
-        (self.0 & CHORD_TYPE_MASK) >> CHORD_TYPE_SHIFT
+    pub fn get(&self) -> u16 {
+        self.0 as u16
     }
+}
 
-    pub fn set_chord_type(&mut self, chord_type: u16) {
-        if chord_type > MAX_CHORD_TYPE {
-            return;
-        }
+// Serialised in its compact encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u16", into = "u16")]
+pub struct Gene {
+    pub root: PitchClass,
+    pub quality: ChordQuality,
+    pub duration: Duration,
+}
+
+impl Gene {
+
+    pub fn new(root: PitchClass, quality: ChordQuality, duration: Duration) -> Gene {
+        Gene { root, quality, duration }
+    }
+
+    pub fn get_root_note(&self) -> u16 {
+        self.root.get()
+    }
+
+    pub fn set_root_note(&mut self, root_note: u16) -> Result<(), GeneError> {
+        self.root = PitchClass::new(root_note)?;
+        Ok(())
+    }
+
+    pub fn get_chord_type(&self) -> u16 {
+        self.quality.index()
+    }
 
-        self.0 &= !CHORD_TYPE_MASK;
-        self.0 |= chord_type << CHORD_TYPE_SHIFT;
+    pub fn set_chord_type(&mut self, chord_type: u16) -> Result<(), GeneError> {
+        self.quality = ChordQuality::from_index(chord_type)?;
+        Ok(())
     }
 
     pub fn get_duration(&self) -> u16 {
-        ((self.0 & DURATION_MASK) >> DURATION_SHIFT) + 1
+        self.duration.get()
     }
 
-    pub fn set_duration(&mut self, duration: u16) {
-        if duration > MAX_DURATION || duration == 0 {
-            return;
-        }
+    pub fn set_duration(&mut self, duration: u16) -> Result<(), GeneError> {
+        self.duration = Duration::new(duration)?;
+        Ok(())
+    }
+
+    pub fn from_chord(chord: Chord, duration: u16) -> Result<Gene, GeneError> {
+        Ok(Gene::new(chord.root, chord.quality, Duration::new(duration)?))
+    }
 
-        self.0 &= !DURATION_MASK;
-        self.0 |= (duration - 1) << DURATION_SHIFT;
+    pub fn chord(&self) -> Chord {
+        Chord::new(self.root, self.quality)
     }
 
+    // Copy of this gene's chord lasting for a different duration.
+    pub fn with_duration(&self, duration: u16) -> Result<Gene, GeneError> {
+        Ok(Gene::new(self.root, self.quality, Duration::new(duration)?))
+    }
+
+    // Index of the chord into the precomputed chord tables: root note * 2 + chord type.
     pub fn get_chord(&self) -> u16 {
-        ((self.0 & ROOT_NOTE_MASK) + (self.0 & CHORD_TYPE_MASK)) >> CHORD_TYPE_SHIFT
+        self.chord().index()
+    }
+
+    pub fn encode(&self) -> u16 {
+        (self.get_root_note() << ROOT_NOTE_SHIFT)
+            | (self.get_chord_type() << CHORD_TYPE_SHIFT)
+            | ((self.get_duration() - 1) << DURATION_SHIFT)
     }
 
-    pub fn mutate_chord(&mut self, mutation_rate: f64, rng: &mut ThreadRng) {
+    pub fn decode(encoding: u16) -> Result<Gene, GeneError> {
+        if encoding & !(ROOT_NOTE_MASK | CHORD_TYPE_MASK | DURATION_MASK) != 0 {
+            return Err(GeneError::InvalidEncoding(encoding));
+        }
+
+        generate_gene(
+            (encoding & ROOT_NOTE_MASK) >> ROOT_NOTE_SHIFT,
+            (encoding & CHORD_TYPE_MASK) >> CHORD_TYPE_SHIFT,
+            ((encoding & DURATION_MASK) >> DURATION_SHIFT) + 1,
+        )
+    }
+
+    pub fn transpose(&mut self, interval: Interval) {
+        self.root = self.root.transpose(interval);
+    }
+
+    // Swaps a major chord for its relative minor, or a minor chord for its relative major.
+    pub fn substitute_relative(&mut self) {
+        match self.quality {
+            ChordQuality::Major => {
+                self.root = self.root.transpose(Interval::MAJOR_SIXTH);
+                self.quality = ChordQuality::Minor;
+            },
+            ChordQuality::Minor => {
+                self.root = self.root.transpose(Interval::MINOR_THIRD);
+                self.quality = ChordQuality::Major;
+            },
+        }
+    }
+
+    // Swaps a chord for the major chord a tritone away.
+    pub fn substitute_tritone(&mut self) {
+        self.root = self.root.transpose(Interval::TRITONE);
+        self.quality = ChordQuality::Major;
+    }
+
+    pub fn mutate_chord<R: Rng + ?Sized>(&mut self, mutation_rate: f64, rng: &mut R) {
         if rng.random_bool(mutation_rate) {
-            self.set_root_note(rng.random_range(0..(MAX_ROOT_NOTE + 1)));
+            self.root = PitchClass::from_semitones(rng.random_range(0..=MAX_ROOT_NOTE));
         }
 
         if rng.random_bool(mutation_rate) {
-            self.set_chord_type(rng.random_range(0..(MAX_CHORD_TYPE + 1)));
+            self.quality = if rng.random_bool(0.5) { ChordQuality::Major } else { ChordQuality::Minor };
         }
     }
 
-    pub fn mutate_duration_and_return_change(&mut self, mutation_rate: f64, rng: &mut ThreadRng) -> i16 {
+    pub fn mutate_duration_and_return_change<R: Rng + ?Sized>(&mut self, mutation_rate: f64, rng: &mut R) -> i16 {
 
         let mut duration_mutate_mask = 0;
         for i in 0..DURATION_SIZE {
@@ -97,38 +203,59 @@ impl Gene {
             }
         }
 
-        // XOR the mutate mask onto the current duration to invert the mutated bits.
+        // XOR the mutate mask onto the encoded duration to invert the mutated bits.
+        // The encoded duration is duration - 1, so every 5 bit result is a valid duration.
         let original_duration = self.get_duration() - 1;
         let mutated_duration = original_duration ^ duration_mutate_mask;
-        self.set_duration(mutated_duration + 1);
+        self.duration = Duration(mutated_duration as u8 + 1);
 
         mutated_duration as i16 - original_duration as i16
     }
 
-    pub fn print(&self) {
-    
-        let root_note_letter = ROOT_NOTE_LETTERS[self.get_root_note() as usize];
-        let chord_type_name = CHORD_TYPE_NAMES[self.get_chord_type() as usize];
+    // Chord symbol spelt for the key.
+    pub fn print(&self, key: &Key) {
+
+        let chord_symbol = self.chord().symbol(key, &ChordSymbolStyle::default());
         let chord_duration = self.get_duration();
 
         //let notes_in_chord = precomputed_chord_notes::CHORD_NOTES[self.get_chord() as usize];
-        //let notes_in_chord_string = notes_in_chord.iter().map(|n| ROOT_NOTE_LETTERS[*n as usize]).collect::<Vec<_>>().join(", ");
+        //let notes_in_chord_string = notes_in_chord.iter().map(|n| n.name()).collect::<Vec<_>>().join(", ");
 
         //let notes_in_chord_scale = precomputed_chord_notes::CHORD_SCALE_NOTES[self.get_chord() as usize];
-        //let notes_in_chord_scale_string = notes_in_chord_scale.iter().map(|n| ROOT_NOTE_LETTERS[*n as usize]).collect::<Vec<_>>().join(", ");
+        //let notes_in_chord_scale_string = notes_in_chord_scale.iter().map(|n| n.name()).collect::<Vec<_>>().join(", ");
 
-        println!("{root_note_letter} {chord_type_name} for {chord_duration} semiquavers.");
+        println!("{chord_symbol} for {chord_duration} semiquavers.");
         //println!("Notes in chord: {notes_in_chord_string}");
         //println!("Notes in chord scale: {notes_in_chord_scale_string}");
     }
 }
 
+impl TryFrom<u16> for Gene {
+    type Error = GeneError;
+
+    fn try_from(encoding: u16) -> Result<Self, Self::Error> {
+        Gene::decode(encoding)
+    }
+}
+
+impl From<Gene> for u16 {
+    fn from(gene: Gene) -> Self {
+        gene.encode()
+    }
+}
+
 // Factory function to construct a gene with given data.
-pub fn generate_gene(root_note: u16, chord_type: u16, duration: u16) -> Gene {
-    Gene((root_note << ROOT_NOTE_SHIFT) + (chord_type << CHORD_TYPE_SHIFT) + (duration - 1 << DURATION_SHIFT))
+pub fn generate_gene(root_note: u16, chord_type: u16, duration: u16) -> Result<Gene, GeneError> {
+    Ok(Gene::new(PitchClass::new(root_note)?, ChordQuality::from_index(chord_type)?, Duration::new(duration)?))
 }
 
 // Factory function to construct a gene with random data.
-pub fn generate_random_gene(rng: &mut ThreadRng) -> Gene {
-    Gene((rng.random_range(0..12) << ROOT_NOTE_SHIFT) + rng.random_range(0..pow(2, (DURATION_SIZE + CHORD_TYPE_SIZE) as usize)))
-}
\ No newline at end of file
+pub fn generate_random_gene<R: Rng + ?Sized>(rng: &mut R) -> Gene {
+    let quality = if rng.random_bool(0.5) { ChordQuality::Major } else { ChordQuality::Minor };
+
+    Gene::new(
+        PitchClass::from_semitones(rng.random_range(0..=MAX_ROOT_NOTE)),
+        quality,
+        Duration(rng.random_range(1..=MAX_DURATION as u8)),
+    )
+}
@@ -0,0 +1,239 @@
+use rand::Rng;
+
+use crate::composer::{
+    BEAT_DURATION,
+    chromosome::{Chromosome, ChromosomeError, gene::{self, MAX_DURATION}},
+    config::MutationConfig,
+};
+use crate::theory::{ChordQuality, Interval};
+
+impl Chromosome {
+
+    // Applies every mutation operator while keeping the chromosome's total duration unchanged.
+    pub fn mutate<R: Rng + ?Sized>(&mut self, mutation_config: &MutationConfig, rng: &mut R) -> Result<(), ChromosomeError> {
+
+        let duration = self.get_duration();
+
+        self.split_genes(mutation_config.split_rate, rng);
+        self.merge_genes(mutation_config.merge_rate, rng);
+        self.shift_boundaries(mutation_config.shift_boundary_rate, rng);
+        self.transpose_genes(mutation_config.transpose_rate, rng);
+        self.substitute_genes(mutation_config.substitute_rate, rng);
+        self.mutate_chord(mutation_config.chord_rate, rng);
+        self.mutate_duration(mutation_config.duration_rate, rng);
+
+        if !self.is_valid_chromosome(duration) {
+            return Err(ChromosomeError::DurationMismatch { expected: duration, actual: self.get_duration() });
+        }
+
+        Ok(())
+
+    }
+
+    // Splits a gene into two halves, the second half taking a new random chord.
+    fn split_genes<R: Rng + ?Sized>(&mut self, split_rate: f64, rng: &mut R) {
+
+        let mut index: usize = 0;
+        while index < self.0.len() {
+
+            let duration = self.0[index].get_duration();
+
+            if duration > 1 && rng.random_bool(split_rate) {
+
+                let first_half_duration = duration / 2;
+                let first_half = self.0[index].with_duration(first_half_duration).expect("half of a gene is a valid duration");
+                let second_half = gene::generate_random_gene(rng).with_duration(duration - first_half_duration).expect("half of a gene is a valid duration");
+
+                self.0[index] = first_half;
+                self.0.insert(index + 1, second_half);
+
+                index += 1;
+
+            }
+
+            index += 1;
+
+        }
+
+    }
+
+    // Merges a gene into the one before it, keeping the first gene's chord.
+    fn merge_genes<R: Rng + ?Sized>(&mut self, merge_rate: f64, rng: &mut R) {
+
+        let mut index: usize = 1;
+        while index < self.0.len() {
+
+            let merged_duration = self.0[index - 1].get_duration() + self.0[index].get_duration();
+
+            if merged_duration <= MAX_DURATION && rng.random_bool(merge_rate) {
+                self.0[index - 1] = self.0[index - 1].with_duration(merged_duration).expect("merged duration is at most the maximum");
+                self.0.remove(index);
+            }
+            else {
+                index += 1;
+            }
+
+        }
+
+    }
+
+    // Slides the boundary between two neighbouring genes a beat earlier or later.
+    fn shift_boundaries<R: Rng + ?Sized>(&mut self, shift_boundary_rate: f64, rng: &mut R) {
+
+        for index in 1..self.0.len() {
+
+            if !rng.random_bool(shift_boundary_rate) {
+                continue;
+            }
+
+            let (earlier_duration, later_duration) = (self.0[index - 1].get_duration(), self.0[index].get_duration());
+
+            let (shifted_earlier_duration, shifted_later_duration) = if rng.random_bool(0.5) {
+                (earlier_duration + BEAT_DURATION, later_duration.saturating_sub(BEAT_DURATION))
+            } else {
+                (earlier_duration.saturating_sub(BEAT_DURATION), later_duration + BEAT_DURATION)
+            };
+
+            // Skip shifts that would empty a gene or push it past the maximum duration.
+            if let (Ok(earlier_gene), Ok(later_gene)) = (self.0[index - 1].with_duration(shifted_earlier_duration), self.0[index].with_duration(shifted_later_duration)) {
+                self.0[index - 1] = earlier_gene;
+                self.0[index] = later_gene;
+            }
+
+        }
+
+    }
+
+    // Moves a gene's root up or down a perfect fifth.
+    fn transpose_genes<R: Rng + ?Sized>(&mut self, transpose_rate: f64, rng: &mut R) {
+
+        for gene in &mut self.0 {
+
+            if rng.random_bool(transpose_rate) {
+                // Transposing down a fifth is the same as up a fourth.
+                gene.transpose(if rng.random_bool(0.5) { Interval::PERFECT_FIFTH } else { Interval::PERFECT_FOURTH });
+            }
+
+        }
+
+    }
+
+    // Swaps a gene's chord for its relative major or minor, or for a tritone substitution.
+    fn substitute_genes<R: Rng + ?Sized>(&mut self, substitute_rate: f64, rng: &mut R) {
+
+        for gene in &mut self.0 {
+
+            if !rng.random_bool(substitute_rate) {
+                continue;
+            }
+
+            if gene.quality == ChordQuality::Major && rng.random_bool(0.5) {
+                gene.substitute_tritone();
+            }
+            else {
+                gene.substitute_relative();
+            }
+
+        }
+
+    }
+
+    fn mutate_chord<R: Rng + ?Sized>(&mut self, mutation_rate: f64, rng: &mut R) {
+        
+        for gene in &mut self.0 {
+
+            gene.mutate_chord(mutation_rate, rng);
+
+        }
+
+    }
+
+    fn mutate_duration<R: Rng + ?Sized>(&mut self, mutation_rate: f64, rng: &mut R) {
+
+        let mut index: usize = 0;
+        while index < self.0.len() {
+
+            let duration_change = self.0[index].mutate_duration_and_return_change(mutation_rate, rng);
+
+            if duration_change > 0 {
+                index = self.take_duration_from_neighbours(index, duration_change as u16);
+            }
+            else if duration_change < 0 {
+                self.give_duration_to_neighbours(index, duration_change.unsigned_abs());
+            }
+
+            index += 1;
+
+        }
+    }
+
+    // Removes `excess` semiquavers from the genes after `index`, then the genes before it,
+    // dropping any gene that is used up entirely. Returns the new index of the grown gene.
+    fn take_duration_from_neighbours(&mut self, mut index: usize, mut excess: u16) -> usize {
+
+        while excess > 0 && index + 1 < self.0.len() {
+
+            let neighbour_duration = self.0[index + 1].get_duration();
+
+            if neighbour_duration > excess {
+                self.0[index + 1] = self.0[index + 1].with_duration(neighbour_duration - excess).expect("shortened duration is non-zero");
+                excess = 0;
+            }
+            else {
+                excess -= neighbour_duration;
+                self.0.remove(index + 1);
+            }
+        }
+
+        while excess > 0 && index > 0 {
+
+            let neighbour_duration = self.0[index - 1].get_duration();
+
+            if neighbour_duration > excess {
+                self.0[index - 1] = self.0[index - 1].with_duration(neighbour_duration - excess).expect("shortened duration is non-zero");
+                excess = 0;
+            }
+            else {
+                excess -= neighbour_duration;
+                self.0.remove(index - 1);
+                index -= 1;
+            }
+        }
+
+        // Every other gene has been used up, so undo what is left of the growth.
+        // The remaining excess is at most the growth, so the gene keeps at least its original duration.
+        if excess > 0 {
+            let duration = self.0[index].get_duration();
+            self.0[index] = self.0[index].with_duration(duration - excess).expect("gene keeps at least its original duration");
+        }
+
+        index
+
+    }
+
+    // Adds `shortfall` semiquavers to the next gene, then the previous gene, up to the maximum duration.
+    // Anything left over continues the shrunk gene's chord in a new gene straight after it.
+    fn give_duration_to_neighbours(&mut self, index: usize, mut shortfall: u16) {
+
+        for neighbour_index in [index + 1, index.wrapping_sub(1)] {
+
+            if shortfall == 0 || neighbour_index >= self.0.len() {
+                continue;
+            }
+
+            let neighbour_duration = self.0[neighbour_index].get_duration();
+            let added_duration = shortfall.min(MAX_DURATION - neighbour_duration);
+
+            self.0[neighbour_index] = self.0[neighbour_index].with_duration(neighbour_duration + added_duration).expect("grown duration is at most the maximum");
+            shortfall -= added_duration;
+
+        }
+
+        // A single gene never shrinks by more than MAX_DURATION - 1, so one new gene is enough.
+        if shortfall > 0 {
+            let continuation = self.0[index].with_duration(shortfall).expect("shortfall is a valid duration");
+            self.0.insert(index + 1, continuation);
+        }
+
+    }
+}
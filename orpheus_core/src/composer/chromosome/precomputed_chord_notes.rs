@@ -1,55 +1,70 @@
-// Arrays of notes contained in every chord.
-pub static CHORD_NOTES: [&[u16]; 24] = [
-    &[4, 7],        // C major triad
-    &[3, 7],        // C minor triad
-    &[5, 8],        // D♭ major triad
-    &[4, 8],        // C♯ minor triad
-    &[6, 9],        // ...
-    &[5, 9],
-    &[7, 10],
-    &[6, 10],
-    &[8, 11],
-    &[7, 11],
-    &[9, 0],
-    &[8, 0],
-    &[10, 1],
-    &[9, 1],
-    &[11, 2],
-    &[10, 2],
-    &[0, 3],
-    &[11, 3],
-    &[1, 4],
-    &[0, 4],
-    &[2, 5],
-    &[1, 5],
-    &[3, 6],        // ...
-    &[2, 6]         // B minor triad
-];
-
-// Arrays of notes contained in every chord's scale.
-pub const CHORD_SCALE_NOTES: [[u16; 7]; 24] = [
-    [0, 2, 4, 5, 7, 9, 11],        // C major
-    [0, 2, 3, 5, 7, 8, 11],        // C minor
-    [0, 1, 3, 5, 6, 8, 10],        // D♭ major
-    [0, 1, 3, 4, 6, 7, 9],         // C♯ minor
-    [1, 2, 4, 6, 7, 9, 11],        // ...
-    [1, 2, 4, 5, 7, 9, 10],
-    [0, 2, 3, 5, 7, 8, 10],
-    [2, 3, 5, 6, 8, 10, 11],
-    [1, 3, 4, 6, 8, 9, 11],
-    [0, 3, 4, 6, 7, 9, 11],
-    [0, 2, 4, 5, 7, 9, 10],
-    [0, 1, 4, 5, 7, 8, 10],
-    [1, 3, 5, 6, 8, 10, 11],
-    [1, 2, 5, 6, 8, 9, 11],
-    [0, 2, 4, 6, 7, 9, 11],
-    [0, 2, 3, 6, 7, 9, 10],
-    [0, 1, 3, 5, 7, 8, 10],
-    [1, 3, 4, 7, 8, 10, 11],
-    [1, 2, 4, 6, 8, 9, 11],
-    [0, 2, 4, 5, 8, 9, 11],
-    [0, 2, 3, 5, 7, 9, 10],
-    [0, 1, 3, 5, 6, 9, 10],
-    [1, 3, 4, 6, 8, 10, 11],        // ...
-    [1, 2, 4, 6, 7, 10, 11]         // B minor
-];
\ No newline at end of file
+use crate::theory::{Chord, Key, PitchClass};
+
+// Chord tables are generated at compile time from the theory module's interval formulas,
+// indexed by Chord::index: root note * 2 + chord type. Keys are indexed the same way, tonic * 2 + quality.
+pub const CHORD_COUNT: usize = Chord::COUNT;
+pub const KEY_COUNT: usize = Chord::COUNT;
+
+// Notes contained in every chord, root first.
+pub static CHORD_NOTES: [[PitchClass; 3]; CHORD_COUNT] = build_chord_notes();
+
+// Notes contained in every chord's scale in every key, root first.
+pub static CHORD_SCALE_NOTES: [[[PitchClass; 7]; CHORD_COUNT]; KEY_COUNT] = build_chord_scale_notes();
+
+pub fn chord_notes(chord: Chord) -> &'static [PitchClass] {
+    &CHORD_NOTES[chord.index() as usize]
+}
+
+// Notes of the chord's scale for its function in the key.
+pub fn chord_scale_notes(chord: Chord, key: &Key) -> &'static [PitchClass] {
+    &CHORD_SCALE_NOTES[key_index(key)][chord.index() as usize]
+}
+
+fn key_index(key: &Key) -> usize {
+    Chord::new(key.tonic, key.quality).index() as usize
+}
+
+const fn chord_at(index: usize) -> Chord {
+    match Chord::from_index(index as u16) {
+        Ok(chord) => chord,
+        Err(_) => panic!("chord index is below the chord count"),
+    }
+}
+
+const fn build_chord_notes() -> [[PitchClass; 3]; CHORD_COUNT] {
+
+    let mut table = [[PitchClass::from_semitones(0); 3]; CHORD_COUNT];
+    let mut chord = 0;
+
+    while chord < CHORD_COUNT {
+        table[chord] = chord_at(chord).pitch_classes();
+        chord += 1;
+    }
+
+    table
+
+}
+
+const fn build_chord_scale_notes() -> [[[PitchClass; 7]; CHORD_COUNT]; KEY_COUNT] {
+
+    let mut table = [[[PitchClass::from_semitones(0); 7]; CHORD_COUNT]; KEY_COUNT];
+    let mut key = 0;
+
+    while key < KEY_COUNT {
+
+        let tonic_chord = chord_at(key);
+        let table_key = Key::new(tonic_chord.root, tonic_chord.quality);
+
+        let mut chord = 0;
+        while chord < CHORD_COUNT {
+            table[key][chord] = table_key.chord_scale(&chord_at(chord)).pitch_classes();
+            chord += 1;
+        }
+
+        key += 1;
+
+    }
+
+    table
+
+}
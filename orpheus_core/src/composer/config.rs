@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::composer::{adaptation::MutationSchedule, chromosome::fitness::FitnessWeights, constraints::Constraint, pareto::Optimisation, crossover::Crossover, results::ChordDistance, seeding::{self, ProgressionError, SeedingConfig}, selection::Selection, stopping::StoppingCriteria};
+use crate::theory::Key;
+
+const INITIAL_POOL_SIZE: usize = 80;
+const SELECTION_POOL_SIZE: usize = 30;
+const OFFSPRING_TARGET: usize = 74;
+const ELITE_POOL_SIZE: usize = 6;
+
+const FITNESS_SHARING_RADIUS: f64 = 0.2;
+
+const RESULT_COUNT: usize = 5;
+const MINIMUM_RESULT_DISTANCE: f64 = 0.25;
+
+const MUTATION_RATE: f64 = 0.05;
+const STRUCTURAL_MUTATION_RATE: f64 = 0.02;
+
+// A config that would leave the population or the parents to breed from empty,
+// a user progression to seed from that doesn't parse, or a melody with no chords to fit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    EmptyInitialPool,
+    // Neither offspring nor elite chromosomes would make up the next generation.
+    EmptyNextGeneration,
+    // Offspring are wanted but no parents are selected to breed them.
+    EmptySelectionPool,
+    // Fitness sharing radius that isn't a positive finite distance.
+    InvalidSharingRadius,
+    InvalidProgression(ProgressionError),
+    // An island model given no configs to build islands from.
+    NoIslands,
+    // The melody's notes add up to no duration, so a progression would have no chords.
+    EmptyMelody,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::EmptyInitialPool => write!(f, "initial pool size must be at least 1"),
+            ConfigError::EmptyNextGeneration => write!(f, "offspring target and elite pool size can't both be 0"),
+            ConfigError::EmptySelectionPool => write!(f, "selection pool size must be at least 1 when breeding offspring"),
+            ConfigError::InvalidSharingRadius => write!(f, "fitness sharing radius must be a positive distance"),
+            ConfigError::InvalidProgression(error) => write!(f, "seeding progression is invalid: {error}"),
+            ConfigError::NoIslands => write!(f, "island model needs at least one config"),
+            ConfigError::EmptyMelody => write!(f, "melody must last at least a semiquaver"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<ProgressionError> for ConfigError {
+    fn from(error: ProgressionError) -> Self {
+        ConfigError::InvalidProgression(error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub initial_pool_size: usize,
+    pub selection_pool_size: usize,
+    pub offspring_target: usize,
+    pub elite_pool_size: usize,
+    pub mutation: MutationConfig,
+    // How the mutation rates are scaled as the run goes on.
+    pub mutation_schedule: MutationSchedule,
+    pub crossover: Crossover,
+    pub selection: Selection,
+    // Distance within which chromosomes share fitness, or None to disable fitness sharing.
+    pub fitness_sharing_radius: Option<f64>,
+    // Whether repeated progressions are replaced with random chromosomes every generation.
+    pub remove_duplicates: bool,
+    pub result_count: usize,
+    pub minimum_result_distance: f64,
+    pub chord_distance: ChordDistance,
+    pub stopping: StoppingCriteria,
+    // Whether the initial pool starts with the harmonizer's exact chord-melody optimum.
+    pub seed_with_harmonization: bool,
+    // Heuristic and user-supplied progressions mixed into the initial pool.
+    pub seeding: SeedingConfig,
+    // Locked chords and restricted ranges every progression in the run keeps to.
+    pub constraints: Vec<Constraint>,
+    // How much each fitness term counts, including user ratings.
+    pub fitness_weights: FitnessWeights,
+    // Key of the song, which decides the scale each chord's melody notes are judged against,
+    // or None to detect it from the melody.
+    pub key: Option<Key>,
+    pub optimisation: Optimisation,
+    // Seed for the random number generator, or None for a different run every time.
+    pub seed: Option<u64>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            initial_pool_size: INITIAL_POOL_SIZE,
+            selection_pool_size: SELECTION_POOL_SIZE,
+            offspring_target: OFFSPRING_TARGET,
+            elite_pool_size: ELITE_POOL_SIZE,
+            mutation: MutationConfig::default(),
+            mutation_schedule: MutationSchedule::default(),
+            crossover: Crossover::default(),
+            selection: Selection::default(),
+            fitness_sharing_radius: Some(FITNESS_SHARING_RADIUS),
+            remove_duplicates: true,
+            result_count: RESULT_COUNT,
+            minimum_result_distance: MINIMUM_RESULT_DISTANCE,
+            chord_distance: ChordDistance::default(),
+            stopping: StoppingCriteria::default(),
+            seed_with_harmonization: false,
+            seeding: SeedingConfig::default(),
+            constraints: Vec::new(),
+            fitness_weights: FitnessWeights::default(),
+            key: None,
+            optimisation: Optimisation::default(),
+            seed: None,
+        }
+    }
+}
+
+impl Config {
+
+    // Checks the pool sizes can't leave a generation without any chromosomes,
+    // that fitness sharing can't divide fitness by an empty niche, and that every user progression parses.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+
+        if self.initial_pool_size == 0 {
+            return Err(ConfigError::EmptyInitialPool);
+        }
+
+        if self.offspring_target == 0 && self.elite_pool_size == 0 {
+            return Err(ConfigError::EmptyNextGeneration);
+        }
+
+        if self.offspring_target > 0 && self.selection_pool_size == 0 {
+            return Err(ConfigError::EmptySelectionPool);
+        }
+
+        if let Some(sharing_radius) = self.fitness_sharing_radius && !(sharing_radius.is_finite() && sharing_radius > 0.0) {
+            return Err(ConfigError::InvalidSharingRadius);
+        }
+
+        self.seeding.validate()?;
+
+        Ok(())
+
+    }
+
+    // The configured key, or the one detected from the melody.
+    pub fn key_for(&self, melody: &[(u16, u16)]) -> Key {
+        self.key.unwrap_or_else(|| seeding::detect_key(melody))
+    }
+}
+
+// Probability of each mutation operator firing.
+// Gene operators roll once per gene, boundary operators once per pair of neighbouring genes,
+// and duration mutation once per bit of each gene's encoded duration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MutationConfig {
+    pub chord_rate: f64,
+    pub duration_rate: f64,
+    pub split_rate: f64,
+    pub merge_rate: f64,
+    pub shift_boundary_rate: f64,
+    pub transpose_rate: f64,
+    pub substitute_rate: f64,
+}
+
+impl MutationConfig {
+
+    // Every operator firing with the same probability.
+    pub fn uniform(mutation_rate: f64) -> MutationConfig {
+        MutationConfig {
+            chord_rate: mutation_rate,
+            duration_rate: mutation_rate,
+            split_rate: mutation_rate,
+            merge_rate: mutation_rate,
+            shift_boundary_rate: mutation_rate,
+            transpose_rate: mutation_rate,
+            substitute_rate: mutation_rate,
+        }
+    }
+}
+
+impl Default for MutationConfig {
+    fn default() -> Self {
+        MutationConfig {
+            chord_rate: MUTATION_RATE,
+            duration_rate: MUTATION_RATE,
+            split_rate: STRUCTURAL_MUTATION_RATE,
+            merge_rate: STRUCTURAL_MUTATION_RATE,
+            shift_boundary_rate: STRUCTURAL_MUTATION_RATE,
+            transpose_rate: STRUCTURAL_MUTATION_RATE,
+            substitute_rate: STRUCTURAL_MUTATION_RATE,
+        }
+    }
+}
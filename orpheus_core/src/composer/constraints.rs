@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::composer::{
+    chromosome::{self, Chromosome, gene::Gene},
+};
+use crate::theory::{Chord, Key};
+
+// Restriction on part of every progression in a compose run, with times in semiquavers from the start of the melody.
+// Constraints are enforced after every operator that creates or changes chromosomes, so locked regions never change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Constraint {
+    // The chord playing throughout start..end, such as Am for the third bar.
+    LockChord { start: u16, end: u16, chord: Chord },
+    // Exact genes played one after another from `start`.
+    LockGenes { start: u16, genes: Vec<Gene> },
+    // Only chords belonging to the key anywhere in start..end.
+    DiatonicOnly { start: u16, end: u16, key: Key },
+}
+
+impl Constraint {
+
+    // Locks the given genes of a chromosome where they are, such as the first four chords of a progression the user likes.
+    pub fn lock_genes(chromosome: &Chromosome, genes: Range<usize>) -> Constraint {
+
+        let start = chromosome.get_genes()[..genes.start].iter().map(|gene| gene.get_duration()).sum();
+
+        Constraint::LockGenes {
+            start,
+            genes: chromosome.get_genes()[genes].to_vec(),
+        }
+
+    }
+
+    fn apply(&self, genes: &mut Vec<Gene>) {
+
+        let duration: u16 = genes.iter().map(|gene| gene.get_duration()).sum();
+
+        match self {
+            Constraint::LockChord { start, end, chord } => {
+                for gene in genes_within(genes, *start, (*end).min(duration)) {
+                    gene.root = chord.root;
+                    gene.quality = chord.quality;
+                }
+            },
+            Constraint::LockGenes { start, genes: locked_genes } => {
+                let start = (*start).min(duration);
+                let locked_genes = truncate_to_duration(locked_genes, duration - start);
+                let end = start + locked_genes.iter().map(|gene| gene.get_duration()).sum::<u16>();
+
+                split_at_time(genes, start);
+                split_at_time(genes, end);
+
+                let first_index = gene_index_at_time(genes, start);
+                let last_index = gene_index_at_time(genes, end);
+                genes.splice(first_index..last_index, locked_genes);
+            },
+            Constraint::DiatonicOnly { start, end, key } => {
+                let diatonic_chords = key.diatonic_chords();
+
+                for gene in genes_within(genes, *start, (*end).min(duration)) {
+                    if diatonic_chords.contains(&gene.chord()) {
+                        continue;
+                    }
+
+                    // Nearest diatonic root, keeping the chord's quality where the choice is otherwise even.
+                    let &chord = diatonic_chords.iter()
+                        .min_by_key(|chord| (gene.root.interval_to(chord.root).shortest_semitones(), chord.quality != gene.quality))
+                        .expect("every key has diatonic chords");
+
+                    *gene = Gene::from_chord(chord, gene.get_duration()).expect("diatonic chord keeps the gene's duration");
+                }
+            },
+        }
+
+    }
+}
+
+// Rewrites the chromosome so it meets every constraint, with later constraints taking priority where they overlap.
+// Locks are applied after diatonic ranges so a locked chord is never changed.
+pub fn enforce_constraints(constraints: &[Constraint], chromosome: &mut Chromosome) {
+
+    if constraints.is_empty() {
+        return;
+    }
+
+    let mut genes = chromosome.get_genes().to_vec();
+
+    let (diatonic_constraints, lock_constraints): (Vec<&Constraint>, Vec<&Constraint>) = constraints.iter()
+        .partition(|constraint| matches!(constraint, Constraint::DiatonicOnly { .. }));
+
+    for constraint in diatonic_constraints.into_iter().chain(lock_constraints) {
+        constraint.apply(&mut genes);
+    }
+
+    if genes != chromosome.get_genes() {
+        *chromosome = chromosome::generate_chromosome(genes);
+    }
+
+}
+
+pub fn enforce_pool_constraints(constraints: &[Constraint], chromosome_pool: &mut [Chromosome]) {
+
+    for chromosome in chromosome_pool {
+        enforce_constraints(constraints, chromosome);
+    }
+
+}
+
+// Splits the genes at both ends of the range and returns the genes inside it.
+fn genes_within(genes: &mut Vec<Gene>, start: u16, end: u16) -> &mut [Gene] {
+
+    if start >= end {
+        return &mut [];
+    }
+
+    split_at_time(genes, start);
+    split_at_time(genes, end);
+
+    let first_index = gene_index_at_time(genes, start);
+    let last_index = gene_index_at_time(genes, end);
+
+    &mut genes[first_index..last_index]
+
+}
+
+// Splits the gene playing across `time` in two, so a gene starts exactly at `time`.
+fn split_at_time(genes: &mut Vec<Gene>, time: u16) {
+
+    let mut gene_start = 0;
+
+    for index in 0..genes.len() {
+
+        let gene_end = gene_start + genes[index].get_duration();
+
+        if gene_start < time && time < gene_end {
+            let first_part = genes[index].with_duration(time - gene_start).expect("split is shorter than the gene");
+            let second_part = genes[index].with_duration(gene_end - time).expect("split is shorter than the gene");
+
+            genes.splice(index..=index, [first_part, second_part]);
+            return;
+        }
+
+        gene_start = gene_end;
+
+    }
+
+}
+
+// Index of the first gene starting at or after `time`.
+fn gene_index_at_time(genes: &[Gene], time: u16) -> usize {
+
+    let mut gene_start = 0;
+
+    for (index, gene) in genes.iter().enumerate() {
+
+        if gene_start >= time {
+            return index;
+        }
+
+        gene_start += gene.get_duration();
+
+    }
+
+    genes.len()
+
+}
+
+fn truncate_to_duration(genes: &[Gene], duration: u16) -> Vec<Gene> {
+
+    let mut truncated_genes = Vec::<Gene>::new();
+    let mut total_duration = 0;
+
+    for gene in genes {
+
+        if total_duration >= duration {
+            break;
+        }
+
+        let gene = gene.with_duration(gene.get_duration().min(duration - total_duration)).expect("truncated duration is positive");
+        total_duration += gene.get_duration();
+        truncated_genes.push(gene);
+
+    }
+
+    truncated_genes
+
+}
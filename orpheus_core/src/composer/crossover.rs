@@ -0,0 +1,81 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::composer::{BAR_DURATION, chromosome::{self, Chromosome, gene::Gene}};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Crossover {
+    // One random cut point anywhere in the progression.
+    #[default]
+    SinglePoint,
+    // Two random cut points, swapping the section between them.
+    TwoPoint,
+    // One random cut point on a bar line.
+    BarAligned,
+    // Every bar taken from either parent at random.
+    UniformByBar,
+}
+
+pub fn crossover_parents<R: Rng + ?Sized>(parent_one: &Chromosome, parent_two: &Chromosome, crossover: Crossover, rng: &mut R) -> (Chromosome, Chromosome) {
+
+    let mut child_one_genes = Vec::<Gene>::new();
+    let mut child_two_genes = Vec::<Gene>::new();
+
+    let mut segment_start = 0;
+
+    for (segment_end, swapped) in choose_segments(crossover, parent_one.get_duration(), rng) {
+
+        let parent_one_segment = extract_segment(parent_one, segment_start, segment_end);
+        let parent_two_segment = extract_segment(parent_two, segment_start, segment_end);
+
+        if swapped {
+            child_one_genes.extend(parent_two_segment);
+            child_two_genes.extend(parent_one_segment);
+        }
+        else {
+            child_one_genes.extend(parent_one_segment);
+            child_two_genes.extend(parent_two_segment);
+        }
+
+        segment_start = segment_end;
+
+    }
+
+    (chromosome::generate_chromosome(child_one_genes), chromosome::generate_chromosome(child_two_genes))
+
+}
+
+// Splits the progression into segments, returning each segment's end time
+// and whether child one takes that segment from parent two.
+fn choose_segments<R: Rng + ?Sized>(crossover: Crossover, duration: u16, rng: &mut R) -> Vec<(u16, bool)> {
+
+    let number_of_bars = duration.div_ceil(BAR_DURATION);
+
+    match crossover {
+        Crossover::SinglePoint if duration > 1 => {
+            vec![(rng.random_range(1..duration), false), (duration, true)]
+        },
+        Crossover::TwoPoint if duration > 2 => {
+            let first_cut_point = rng.random_range(1..duration - 1);
+            let second_cut_point = rng.random_range(first_cut_point + 1..duration);
+            vec![(first_cut_point, false), (second_cut_point, true), (duration, false)]
+        },
+        Crossover::BarAligned if number_of_bars > 1 => {
+            vec![(rng.random_range(1..number_of_bars) * BAR_DURATION, false), (duration, true)]
+        },
+        Crossover::UniformByBar => {
+            (1..=number_of_bars)
+                .map(|bar| ((bar * BAR_DURATION).min(duration), rng.random_bool(0.5)))
+                .collect()
+        },
+        // Too short to cut, so the children are copies of their parents.
+        _ => vec![(duration, false)],
+    }
+
+}
+
+fn extract_segment(chromosome: &Chromosome, start: u16, end: u16) -> Vec<Gene> {
+    let (_, from_start) = chromosome.split_at_time(start);
+    let (segment, _) = chromosome::generate_chromosome(from_start).split_at_time(end - start);
+    segment
+}
@@ -0,0 +1,335 @@
+pub mod checkpoint;
+
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::composer::{
+    self,
+    adaptation::MutationSchedule,
+    chromosome::{Chromosome, fitness::FitnessBreakdown},
+    config::{Config, ConfigError},
+    constraints,
+    niching,
+    pareto::{self, Objective, Optimisation, ParetoCandidate},
+    progress::{CancellationToken, Control, GenerationStats},
+    ratings::{self, Rating},
+    results::{self, Candidate},
+    seeding,
+    stopping::{StopReason, StoppingCriteria, StoppingMonitor},
+};
+use crate::theory::Key;
+
+// Genetic algorithm state for one melody, evolved a generation at a time.
+// The population always has its fitness calculated and is sorted from fittest to least fit,
+// so it can be inspected between generations.
+#[derive(Debug, Clone)]
+pub struct GeneticComposer {
+    config: Config,
+    melody: Vec<(u16, u16)>,
+    melody_duration: u16,
+    key: Key,
+    population: Vec<Chromosome>,
+    generation: usize,
+    // Generation the current run stops at, counted from the start of the first run.
+    max_generations: usize,
+    ratings: Vec<Rating>,
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl GeneticComposer {
+
+    // Starts from an initial population seeded as config.seeding describes, with the random number generator
+    // seeded from config.seed if it is set. Fails if the config could leave the population empty or the melody has no duration.
+    pub fn new(melody: &[(u16, u16)], config: &Config) -> Result<GeneticComposer, ConfigError> {
+
+        config.validate()?;
+
+        let melody_duration = composer::calculate_melody_duration(melody);
+        if melody_duration == 0 {
+            return Err(ConfigError::EmptyMelody);
+        }
+
+        let seed = config.seed.unwrap_or_else(|| rand::rng().next_u64());
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        let mut population = seeding::generate_seeded_chromosome_pool(&mut rng, config, melody);
+        constraints::enforce_pool_constraints(&config.constraints, &mut population);
+
+        let mut genetic_composer = GeneticComposer {
+            config: config.clone(),
+            melody: melody.to_vec(),
+            melody_duration,
+            key: config.key_for(melody),
+            population,
+            generation: 0,
+            max_generations: config.stopping.max_generations,
+            ratings: Vec::new(),
+            seed,
+            rng,
+        };
+
+        genetic_composer.calculate_fitness();
+
+        Ok(genetic_composer)
+
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn melody(&self) -> &[(u16, u16)] {
+        &self.melody
+    }
+
+    // Key the melody's notes are judged in, configured or detected.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    pub fn population(&self) -> &[Chromosome] {
+        &self.population
+    }
+
+    // Number of generations evolved so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn ratings(&self) -> &[Rating] {
+        &self.ratings
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn best(&self) -> &Chromosome {
+        &self.population[0]
+    }
+
+    // Stats against the generation limit of the last run, or of the configured run if there hasn't been one.
+    pub fn stats(&self) -> GenerationStats {
+        self.generation_stats(self.max_generations.max(self.generation), true)
+    }
+
+    // A diverse selection of the best progressions in the current population,
+    // or of its Pareto front when optimising with NSGA-II.
+    pub fn candidates(&self) -> Vec<Candidate> {
+
+        let pool = match self.config.optimisation {
+            Optimisation::WeightedSum => self.population.clone(),
+            Optimisation::Nsga2 { .. } => self.pareto_front().into_iter().map(|candidate| candidate.chromosome).collect(),
+        };
+
+        results::select_diverse_results(&pool, self.config.result_count, self.config.minimum_result_distance, self.config.chord_distance)
+
+    }
+
+    // Progressions in the current population that no other progression beats on every objective, fittest first.
+    pub fn pareto_front(&self) -> Vec<ParetoCandidate> {
+
+        let objectives = self.config.optimisation.objectives();
+        let points = self.objective_values(&self.population, objectives);
+
+        pareto::non_dominated_fronts(&points)
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|index| ParetoCandidate {
+                chromosome: self.population[index].clone(),
+                objectives: objectives.iter().copied().zip(points[index].iter().copied()).collect(),
+            })
+            .collect()
+
+    }
+
+    // Evolves the population by a single generation.
+    pub fn step(&mut self) {
+        self.step_with_diversity(self.diversity(false));
+    }
+
+    // As step, with the population's diversity already measured if the mutation schedule needs it.
+    fn step_with_diversity(&mut self, diversity: Option<f64>) {
+
+        let mutation_scale = self.mutation_scale(diversity);
+
+        match &self.config.optimisation {
+            Optimisation::WeightedSum => self.step_weighted_sum(mutation_scale),
+            Optimisation::Nsga2 { objectives } => self.step_nsga2(&objectives.clone(), mutation_scale),
+        }
+
+        self.generation += 1;
+
+    }
+
+    fn step_weighted_sum(&mut self, mutation_scale: f64) {
+
+        let mutation = self.config.mutation.scaled(mutation_scale);
+
+        let config = &self.config;
+
+        let elite = composer::select_top_n_pool(&self.population, config.elite_pool_size);
+
+        if let Some(sharing_radius) = config.fitness_sharing_radius {
+            niching::share_fitness(&mut self.population, sharing_radius, config.chord_distance);
+        }
+
+        let selection_pool = config.selection.select(&self.population, config.selection_pool_size, &mut self.rng);
+
+        let mut offspring = composer::crossover_selection(&mut self.rng, &selection_pool, config.offspring_target, config.crossover);
+        composer::mutate_chromosome_pool(&mut self.rng, &mut offspring, &mutation).expect("mutation preserves chromosome duration");
+        constraints::enforce_pool_constraints(&config.constraints, &mut offspring);
+
+        self.population = offspring;
+        self.population.extend(elite);
+
+        self.remove_duplicates();
+        self.calculate_fitness();
+
+    }
+
+    // Parents are chosen by crowded tournament, then the best fronts of parents and offspring together survive.
+    fn step_nsga2(&mut self, objectives: &[Objective], mutation_scale: f64) {
+
+        let mutation = self.config.mutation.scaled(mutation_scale);
+
+        let (ranks, crowding) = pareto::rank_and_crowding(&self.objective_values(&self.population, objectives));
+        let parents: Vec<Chromosome> = pareto::crowded_tournament(&ranks, &crowding, self.config.selection_pool_size, &mut self.rng)
+            .into_iter()
+            .map(|index| self.population[index].clone())
+            .collect();
+
+        let config = &self.config;
+
+        let mut offspring = composer::crossover_selection(&mut self.rng, &parents, config.offspring_target, config.crossover);
+        composer::mutate_chromosome_pool(&mut self.rng, &mut offspring, &mutation).expect("mutation preserves chromosome duration");
+        constraints::enforce_pool_constraints(&config.constraints, &mut offspring);
+
+        self.population.extend(offspring);
+        self.remove_duplicates();
+        self.calculate_fitness();
+
+        let survivors = pareto::select_survivors(&self.objective_values(&self.population, objectives), self.config.initial_pool_size);
+        let mut population: Vec<Chromosome> = survivors.into_iter().map(|index| self.population[index].clone()).collect();
+
+        composer::sort_pool_by_fitness(&mut population);
+        self.population = population;
+
+    }
+
+    // Replaces repeated progressions with random ones, if the config asks for it.
+    fn remove_duplicates(&mut self) {
+
+        if !self.config.remove_duplicates {
+            return;
+        }
+
+        niching::remove_duplicates(&mut self.population);
+        let remaining_population_size = self.population.len();
+        composer::refill_chromosome_pool(&mut self.rng, &mut self.population, self.config.initial_pool_size, self.melody_duration);
+        constraints::enforce_pool_constraints(&self.config.constraints, &mut self.population[remaining_population_size..]);
+
+    }
+
+    // Value of every objective for every chromosome in the pool.
+    fn objective_values(&self, chromosome_pool: &[Chromosome], objectives: &[Objective]) -> Vec<Vec<f64>> {
+        chromosome_pool.iter()
+            .map(|chromosome| {
+                let breakdown = self.fitness_breakdown(chromosome);
+                objectives.iter().map(|objective| objective.value(&breakdown)).collect()
+            })
+            .collect()
+    }
+
+    // Every fitness term of the chromosome against this composer's melody, including its rating fitness.
+    pub fn fitness_breakdown(&self, chromosome: &Chromosome) -> FitnessBreakdown {
+        let mut breakdown = chromosome.fitness_breakdown(&self.melody, &self.key, &self.config.fitness_weights);
+        breakdown.set_rating(ratings::rating_fitness(&self.ratings, chromosome));
+        breakdown
+    }
+
+    // Records the user's rating of a progression, such as one of the candidates, so every later generation
+    // favours progressions like the ones they liked and avoids ones like those they disliked.
+    pub fn rate(&mut self, rating: Rating) {
+        self.ratings.push(rating);
+        self.calculate_fitness();
+    }
+
+    // Replaces the least fit chromosomes with migrants from another population of the same melody,
+    // rewritten to meet this composer's constraints.
+    pub fn receive_migrants(&mut self, mut migrants: Vec<Chromosome>) {
+
+        constraints::enforce_pool_constraints(&self.config.constraints, &mut migrants);
+
+        let remaining_population_size = self.population.len().saturating_sub(migrants.len());
+
+        self.population.truncate(remaining_population_size);
+        self.population.extend(migrants);
+
+        self.calculate_fitness();
+
+    }
+
+    // Evolves until a stopping criterion is met, calling the observer before every generation.
+    // The generation limit and stagnation count from the start of this call, so a finished run can be continued.
+    pub fn run_until<F>(&mut self, criteria: &StoppingCriteria, mut observer: F, cancellation_token: &CancellationToken) -> StopReason
+    where
+        F: FnMut(&GenerationStats) -> Control,
+    {
+
+        let mut stopping_monitor = StoppingMonitor::new(criteria, self.generation);
+        self.max_generations = self.generation + criteria.max_generations;
+
+        loop {
+
+            let stats = self.generation_stats(self.max_generations, criteria.minimum_diversity.is_some());
+
+            if observer(&stats) == Control::Stop {
+                return StopReason::ObserverStopped;
+            }
+
+            if cancellation_token.is_cancelled() {
+                return StopReason::Cancelled;
+            }
+
+            if let Some(stop_reason) = stopping_monitor.check(self.generation, stats.best_fitness, stats.diversity) {
+                return stop_reason;
+            }
+
+            // The population hasn't changed since the stats, so their diversity is reused rather than measured again.
+            self.step_with_diversity(stats.diversity);
+
+        }
+
+    }
+
+    // Scale the mutation schedule applies to the mutation rates for the next generation.
+    pub fn current_mutation_scale(&self) -> f64 {
+        self.mutation_scale(self.diversity(false))
+    }
+
+    fn generation_stats(&self, max_generations: usize, measure_diversity: bool) -> GenerationStats {
+        let diversity = self.diversity(measure_diversity);
+        GenerationStats::new(self.generation, max_generations, &self.population, diversity, self.mutation_scale(diversity))
+    }
+
+    // Only diversity-triggered schedules read the diversity, and it is always measured for them.
+    fn mutation_scale(&self, diversity: Option<f64>) -> f64 {
+        self.config.mutation_schedule.scale(self.generation, diversity.unwrap_or(1.0))
+    }
+
+    // Diversity compares every pair in the pool, so it is only measured when asked for or when the mutation schedule depends on it.
+    fn diversity(&self, measure_diversity: bool) -> Option<f64> {
+        let schedule_uses_diversity = matches!(self.config.mutation_schedule, MutationSchedule::DiversityTriggered { .. });
+        (measure_diversity || schedule_uses_diversity).then(|| niching::pool_diversity(&self.population, self.config.chord_distance))
+    }
+
+    fn calculate_fitness(&mut self) {
+        composer::calculate_pool_fitness(&mut self.population, &self.melody, &self.key, &self.config.fitness_weights);
+        ratings::apply_ratings(&self.ratings, &mut self.population, self.config.fitness_weights.rating);
+        composer::sort_pool_by_fitness(&mut self.population);
+    }
+}
@@ -0,0 +1,198 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs::File, io::{self, BufReader, BufWriter, Read, Write}, path::Path};
+
+use crate::composer::{
+    self,
+    chromosome::{self, Chromosome, gene::{self, Gene, GeneError}},
+    config::{Config, ConfigError},
+    genetic_composer::GeneticComposer,
+    ratings::Rating,
+};
+
+// Bumped whenever the layout of the checkpoint changes.
+// Config fields added since a checkpoint was written take their default values.
+// Versions before 3 wrote locked chords as a root note and chord type, and can only be read without them.
+pub const FORMAT_VERSION: u32 = 3;
+// Version 1 stored integer fitness, which is recalculated on load anyway, and may be missing ratings.
+pub const OLDEST_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(io::Error),
+    Format(serde_json::Error),
+    UnsupportedFormatVersion(u32),
+    IncompatibleGeneEncoding(u32),
+    InvalidGene(GeneError),
+    // A chromosome whose durations don't add up to the melody's duration.
+    InvalidChromosome(usize),
+    EmptyPopulation,
+    InvalidConfig(ConfigError),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(error) => write!(f, "checkpoint could not be read or written: {error}"),
+            CheckpointError::Format(error) => write!(f, "checkpoint is not valid: {error}"),
+            CheckpointError::UnsupportedFormatVersion(version) => write!(f, "checkpoint format version {version} is not supported, expected {OLDEST_FORMAT_VERSION} to {FORMAT_VERSION}"),
+            CheckpointError::IncompatibleGeneEncoding(version) => write!(f, "checkpoint uses gene encoding version {version}, expected {}", gene::ENCODING_VERSION),
+            CheckpointError::InvalidGene(error) => write!(f, "checkpoint contains an invalid gene: {error}"),
+            CheckpointError::InvalidChromosome(index) => write!(f, "chromosome {index} in the checkpoint doesn't match the melody's duration"),
+            CheckpointError::EmptyPopulation => write!(f, "checkpoint has an empty population"),
+            CheckpointError::InvalidConfig(error) => write!(f, "checkpoint has an invalid config: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl From<io::Error> for CheckpointError {
+    fn from(error: io::Error) -> Self {
+        CheckpointError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for CheckpointError {
+    fn from(error: serde_json::Error) -> Self {
+        CheckpointError::Format(error)
+    }
+}
+
+impl From<GeneError> for CheckpointError {
+    fn from(error: GeneError) -> Self {
+        CheckpointError::InvalidGene(error)
+    }
+}
+
+impl From<ConfigError> for CheckpointError {
+    fn from(error: ConfigError) -> Self {
+        CheckpointError::InvalidConfig(error)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    format_version: u32,
+    gene_encoding_version: u32,
+    config: Config,
+    melody: Vec<(u16, u16)>,
+    generation: usize,
+    rng_seed: u64,
+    // Position in the random number stream, so a resumed run continues exactly where it left off.
+    rng_word_position: u128,
+    population: Vec<ChromosomeRecord>,
+    // Missing from version 1 checkpoints saved before ratings were added.
+    #[serde(default)]
+    ratings: Vec<Rating>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ChromosomeRecord {
+    // Compact gene encodings, see gene::Gene::encode.
+    genes: Vec<u16>,
+    // Fitness when saved, for reading the checkpoint by eye. It is recalculated on load.
+    fitness: f64,
+}
+
+impl GeneticComposer {
+
+    pub fn save_checkpoint<P: AsRef<Path>>(&self, path: P) -> Result<(), CheckpointError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_checkpoint(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn load_checkpoint<P: AsRef<Path>>(path: P) -> Result<GeneticComposer, CheckpointError> {
+        GeneticComposer::read_checkpoint(BufReader::new(File::open(path)?))
+    }
+
+    pub fn write_checkpoint<W: Write>(&self, writer: W) -> Result<(), CheckpointError> {
+
+        let checkpoint = Checkpoint {
+            format_version: FORMAT_VERSION,
+            gene_encoding_version: gene::ENCODING_VERSION,
+            config: self.config.clone(),
+            melody: self.melody.clone(),
+            generation: self.generation,
+            rng_seed: self.seed,
+            rng_word_position: self.rng.get_word_pos(),
+            population: self.population.iter()
+                .map(|chromosome| ChromosomeRecord {
+                    genes: chromosome.get_genes().iter().map(|gene| gene.encode()).collect(),
+                    fitness: chromosome.get_fitness(),
+                })
+                .collect(),
+            ratings: self.ratings.clone(),
+        };
+
+        serde_json::to_writer(writer, &checkpoint)?;
+
+        Ok(())
+
+    }
+
+    pub fn read_checkpoint<R: Read>(reader: R) -> Result<GeneticComposer, CheckpointError> {
+
+        let checkpoint: Checkpoint = serde_json::from_reader(reader)?;
+
+        if !(OLDEST_FORMAT_VERSION..=FORMAT_VERSION).contains(&checkpoint.format_version) {
+            return Err(CheckpointError::UnsupportedFormatVersion(checkpoint.format_version));
+        }
+
+        if checkpoint.gene_encoding_version != gene::ENCODING_VERSION {
+            return Err(CheckpointError::IncompatibleGeneEncoding(checkpoint.gene_encoding_version));
+        }
+
+        if checkpoint.population.is_empty() {
+            return Err(CheckpointError::EmptyPopulation);
+        }
+
+        checkpoint.config.validate()?;
+
+        let melody_duration = composer::calculate_melody_duration(&checkpoint.melody);
+        if melody_duration == 0 {
+            return Err(CheckpointError::InvalidConfig(ConfigError::EmptyMelody));
+        }
+
+        let mut population = Vec::<Chromosome>::new();
+
+        for (index, record) in checkpoint.population.into_iter().enumerate() {
+
+            let genes = record.genes.into_iter().map(Gene::decode).collect::<Result<Vec<Gene>, GeneError>>()?;
+
+            let chromosome = chromosome::generate_chromosome(genes);
+            if chromosome.get_duration() != melody_duration {
+                return Err(CheckpointError::InvalidChromosome(index));
+            }
+
+            population.push(chromosome);
+
+        }
+
+        let mut rng = ChaCha8Rng::seed_from_u64(checkpoint.rng_seed);
+        rng.set_word_pos(checkpoint.rng_word_position);
+
+        let mut genetic_composer = GeneticComposer {
+            key: checkpoint.config.key_for(&checkpoint.melody),
+            // A resumed run gets the configured number of generations again.
+            max_generations: checkpoint.generation + checkpoint.config.stopping.max_generations,
+            config: checkpoint.config,
+            melody: checkpoint.melody,
+            melody_duration,
+            population,
+            generation: checkpoint.generation,
+            ratings: checkpoint.ratings,
+            seed: checkpoint.rng_seed,
+            rng,
+        };
+
+        // Fitness is recalculated rather than trusted, so the population is sorted as a fresh run's would be.
+        genetic_composer.calculate_fitness();
+
+        Ok(genetic_composer)
+
+    }
+}
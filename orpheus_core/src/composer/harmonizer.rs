@@ -0,0 +1,146 @@
+use crate::composer::{
+    self,
+    chromosome::{self, Chromosome, fitness::FitnessWeights, gene::{Gene, MAX_DURATION}},
+};
+use crate::theory::{Chord, Key, PitchClass};
+
+const CHORD_COUNT: usize = Chord::COUNT;
+
+// Best progression found by the exact search, and the score it was chosen on.
+#[derive(Debug, Clone)]
+pub struct Harmonization {
+    // Full fitness calculated, so it can be compared directly with the genetic algorithm's results.
+    pub chromosome: Chromosome,
+    // Weighted chord-melody and boundary alignment fitness plus transition scores, the terms the search optimises exactly.
+    pub score: f64,
+}
+
+// The progression with the highest weighted chord-melody and boundary alignment fitness for the melody.
+// Chord and duration entropy depend on the whole progression, so they are left out of the search.
+pub fn harmonize(melody: &[(u16, u16)], key: &Key, weights: &FitnessWeights) -> Option<Harmonization> {
+    harmonize_with_transitions(melody, key, weights, |_, _| 0.0)
+}
+
+// As harmonize, adding transition_score(previous chord, chord) for every pair of neighbouring chords.
+// Viterbi search over (semiquaver, chord) states: each state keeps the best progression
+// covering the melody up to that semiquaver and ending with that chord.
+// Returns None if the melody has no duration.
+pub fn harmonize_with_transitions<F>(melody: &[(u16, u16)], key: &Key, weights: &FitnessWeights, transition_score: F) -> Option<Harmonization>
+where
+    F: Fn(Chord, Chord) -> f64,
+{
+
+    let melody_duration = composer::calculate_melody_duration(melody) as usize;
+
+    if melody_duration == 0 {
+        return None;
+    }
+
+    // Pitch classes of the notes ending on each semiquaver.
+    let mut notes_ending_at = vec![Vec::<PitchClass>::new(); melody_duration + 1];
+    let mut melody_time = 0;
+
+    for note in melody {
+        melody_time += note.1 as usize;
+        notes_ending_at[melody_time].push(PitchClass::from_semitones(note.0));
+    }
+
+    let chords: Vec<Chord> = (0..CHORD_COUNT as u16).map(|index| Chord::from_index(index).expect("index is below the chord count")).collect();
+
+    // Both terms are means over the melody's notes, so every note contributes an equal share.
+    let note_weight = 1.0 / melody.len() as f64;
+
+    // best_scores[time][chord] is the best score for chords covering 0..time with `chord` last,
+    // reached from the chord starting at previous_states[time][chord].
+    let mut best_scores = vec![[f64::NEG_INFINITY; CHORD_COUNT]; melody_duration + 1];
+    let mut previous_states = vec![[(0, 0); CHORD_COUNT]; melody_duration + 1];
+
+    for start in 0..melody_duration {
+
+        // Best score for a chord starting here, including the transition from the chord before it.
+        let mut entry_scores = [0.0; CHORD_COUNT];
+        let mut entry_chords = [0; CHORD_COUNT];
+
+        if start > 0 {
+            for chord in 0..CHORD_COUNT {
+
+                entry_scores[chord] = f64::NEG_INFINITY;
+
+                for (previous_chord, &previous_score) in best_scores[start].iter().enumerate() {
+
+                    if previous_score == f64::NEG_INFINITY {
+                        continue;
+                    }
+
+                    let score = previous_score + transition_score(chords[previous_chord], chords[chord]);
+
+                    if score > entry_scores[chord] {
+                        entry_scores[chord] = score;
+                        entry_chords[chord] = previous_chord;
+                    }
+
+                }
+            }
+        }
+
+        // Fitness of each chord held from `start` to `end`, built up one semiquaver at a time.
+        let mut segment_scores = [0.0; CHORD_COUNT];
+
+        // Notes with no length at the very start are scored against the first chord, and end with it.
+        if start == 0 {
+            for (chord, segment_score) in segment_scores.iter_mut().enumerate() {
+                for &pitch_class in &notes_ending_at[0] {
+                    *segment_score += weights.chord_melody * note_weight * chromosome::melody_note_fitness(chords[chord], pitch_class, key);
+                }
+                *segment_score += weights.boundary_alignment * note_weight * notes_ending_at[0].len() as f64;
+            }
+        }
+
+        for end in (start + 1)..=(start + MAX_DURATION as usize).min(melody_duration) {
+            for chord in 0..CHORD_COUNT {
+
+                for &pitch_class in &notes_ending_at[end] {
+                    segment_scores[chord] += weights.chord_melody * note_weight * chromosome::melody_note_fitness(chords[chord], pitch_class, key);
+                }
+
+                if entry_scores[chord] == f64::NEG_INFINITY {
+                    continue;
+                }
+
+                let boundary_reward = weights.boundary_alignment * note_weight * notes_ending_at[end].len() as f64;
+                let score = entry_scores[chord] + segment_scores[chord] + boundary_reward;
+
+                if score > best_scores[end][chord] {
+                    best_scores[end][chord] = score;
+                    previous_states[end][chord] = (start, entry_chords[chord]);
+                }
+
+            }
+        }
+
+    }
+
+    let (mut chord, &score) = best_scores[melody_duration].iter()
+        .enumerate()
+        .max_by(|(_, score_one), (_, score_two)| score_one.total_cmp(score_two))
+        .expect("there is at least one chord");
+
+    // Trace the chords back from the end of the melody.
+    let mut genes = Vec::new();
+    let mut end = melody_duration;
+
+    while end > 0 {
+        let (start, previous_chord) = previous_states[end][chord];
+        genes.push(Gene::from_chord(chords[chord], (end - start) as u16).expect("duration is in range"));
+        end = start;
+        chord = previous_chord;
+    }
+
+    genes.reverse();
+
+    let mut chromosome = chromosome::generate_chromosome(genes);
+    chromosome.calculate_fitness(melody, key, weights);
+
+    Some(Harmonization { chromosome, score })
+
+}
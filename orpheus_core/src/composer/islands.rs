@@ -0,0 +1,189 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::composer::{
+    self,
+    chromosome::Chromosome,
+    config::{Config, ConfigError},
+    genetic_composer::GeneticComposer,
+    niching,
+    progress::{CancellationToken, Control, GenerationStats},
+    ratings::Rating,
+    results::{self, Candidate},
+    stopping::{StopReason, StoppingCriteria, StoppingMonitor},
+};
+
+const MIGRATION_INTERVAL: usize = 25;
+const MIGRANT_COUNT: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MigrationConfig {
+    // Generations between migrations.
+    pub interval: usize,
+    // Fittest chromosomes each island sends to the next.
+    pub migrant_count: usize,
+}
+
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        MigrationConfig {
+            interval: MIGRATION_INTERVAL,
+            migrant_count: MIGRANT_COUNT,
+        }
+    }
+}
+
+// Several populations evolving side by side, each with its own config,
+// with the fittest chromosomes passed round a ring of islands every few generations.
+#[derive(Debug, Clone)]
+pub struct IslandModel {
+    islands: Vec<GeneticComposer>,
+    migration: MigrationConfig,
+    generation: usize,
+}
+
+impl IslandModel {
+
+    // One island per config. Seeded islands are offset by their index so islands sharing a config still differ.
+    // Fails if there are no configs or any config is invalid.
+    pub fn new(melody: &[(u16, u16)], configs: &[Config], migration: MigrationConfig) -> Result<IslandModel, ConfigError> {
+
+        if configs.is_empty() {
+            return Err(ConfigError::NoIslands);
+        }
+
+        let islands = configs.iter()
+            .enumerate()
+            .map(|(index, config)| {
+                let island_config = Config { seed: config.seed.map(|seed| seed.wrapping_add(index as u64)), ..config.clone() };
+                GeneticComposer::new(melody, &island_config)
+            })
+            .collect::<Result<Vec<GeneticComposer>, ConfigError>>()?;
+
+        Ok(IslandModel {
+            islands,
+            migration,
+            generation: 0,
+        })
+
+    }
+
+    pub fn islands(&self) -> &[GeneticComposer] {
+        &self.islands
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn best(&self) -> &Chromosome {
+        self.islands.iter()
+            .map(|island| island.best())
+            .max_by(|chromosome_one, chromosome_two| chromosome_one.get_fitness().total_cmp(&chromosome_two.get_fitness()))
+            .expect("island model has at least one island")
+    }
+
+    // Every island's population, fittest first.
+    pub fn population(&self) -> Vec<Chromosome> {
+        let mut population: Vec<Chromosome> = self.islands.iter().flat_map(|island| island.population().iter().cloned()).collect();
+        composer::sort_pool_by_fitness(&mut population);
+        population
+    }
+
+    // A diverse selection of the best progressions across all islands, chosen as the first island's config describes.
+    pub fn candidates(&self) -> Vec<Candidate> {
+        let config = self.islands[0].config();
+        results::select_diverse_results(&self.population(), config.result_count, config.minimum_result_distance, config.chord_distance)
+    }
+
+    // The mutation scale is the mean across islands, as each island follows its own schedule.
+    pub fn stats(&self, max_generations: usize) -> GenerationStats {
+        self.generation_stats(max_generations, true)
+    }
+
+    fn generation_stats(&self, max_generations: usize, measure_diversity: bool) -> GenerationStats {
+        let population = self.population();
+        let diversity = measure_diversity.then(|| niching::pool_diversity(&population, self.islands[0].config().chord_distance));
+        let mutation_scale = self.islands.iter()
+            .map(|island| island.current_mutation_scale())
+            .sum::<f64>() / self.islands.len() as f64;
+
+        GenerationStats::new(self.generation, max_generations, &population, diversity, mutation_scale)
+    }
+
+    // Passes the user's rating on to every island.
+    pub fn rate(&mut self, rating: Rating) {
+        for island in &mut self.islands {
+            island.rate(rating.clone());
+        }
+    }
+
+    // Evolves every island by a generation, migrating once every interval.
+    pub fn step(&mut self) {
+
+        #[cfg(not(feature = "parallel"))]
+        self.islands.iter_mut().for_each(|island| island.step());
+
+        #[cfg(feature = "parallel")]
+        self.islands.par_iter_mut().for_each(|island| island.step());
+
+        self.generation += 1;
+
+        if self.migration.interval > 0 && self.generation.is_multiple_of(self.migration.interval) {
+            self.migrate();
+        }
+
+    }
+
+    // Each island's fittest chromosomes replace the least fit chromosomes of the next island round the ring.
+    pub fn migrate(&mut self) {
+
+        if self.islands.len() < 2 {
+            return;
+        }
+
+        let emigrants: Vec<Vec<Chromosome>> = self.islands.iter()
+            .map(|island| island.population().iter().take(self.migration.migrant_count).cloned().collect())
+            .collect();
+
+        let island_count = self.islands.len();
+
+        for (index, migrants) in emigrants.into_iter().enumerate() {
+            self.islands[(index + 1) % island_count].receive_migrants(migrants);
+        }
+
+    }
+
+    // As GeneticComposer::run_until, with stats and stopping measured across all islands.
+    pub fn run_until<F>(&mut self, criteria: &StoppingCriteria, mut observer: F, cancellation_token: &CancellationToken) -> StopReason
+    where
+        F: FnMut(&GenerationStats) -> Control,
+    {
+
+        let mut stopping_monitor = StoppingMonitor::new(criteria, self.generation);
+        let max_generations = self.generation + criteria.max_generations;
+
+        loop {
+
+            let stats = self.generation_stats(max_generations, criteria.minimum_diversity.is_some());
+
+            if observer(&stats) == Control::Stop {
+                return StopReason::ObserverStopped;
+            }
+
+            if cancellation_token.is_cancelled() {
+                return StopReason::Cancelled;
+            }
+
+            if let Some(stop_reason) = stopping_monitor.check(self.generation, stats.best_fitness, stats.diversity) {
+                return stop_reason;
+            }
+
+            self.step();
+
+        }
+
+    }
+}
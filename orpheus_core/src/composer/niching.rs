@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+
+use crate::composer::{chromosome::Chromosome, results::ChordDistance};
+
+// Removes repeated progressions from the pool, keeping the first copy of each.
+pub fn remove_duplicates(chromosome_pool: &mut Vec::<Chromosome>) {
+
+    let mut seen_chromosomes = HashSet::<Chromosome>::new();
+
+    chromosome_pool.retain(|chromosome| seen_chromosomes.insert(chromosome.clone()));
+
+}
+
+// Mean distance between every pair of chromosomes in the pool, from 0 (all the same) to 1.
+pub fn pool_diversity(chromosome_pool: &[Chromosome], chord_distance: ChordDistance) -> f64 {
+
+    let pool_size = chromosome_pool.len();
+
+    if pool_size < 2 {
+        return 0.0;
+    }
+
+    let total_distance: f64 = chord_distance.pairwise_distances(chromosome_pool).iter().flatten().sum();
+
+    total_distance / (pool_size * (pool_size - 1)) as f64
+
+}
+
+// Fitness sharing: every chromosome's fitness is scaled by how crowded its niche is,
+// so a cluster of similar progressions can't take over the selection.
+// Neighbours within `sharing_radius` count towards the niche, closer neighbours counting more.
+pub fn share_fitness(chromosome_pool: &mut [Chromosome], sharing_radius: f64, chord_distance: ChordDistance) {
+
+    let niche_counts: Vec<f64> = chord_distance.pairwise_distances(chromosome_pool)
+        .into_iter()
+        .map(|distances| {
+            distances.into_iter()
+                .filter(|&distance| distance < sharing_radius)
+                .map(|distance| 1.0 - distance / sharing_radius)
+                .sum::<f64>()
+                // A chromosome is always in its own niche.
+                .max(1.0)
+        })
+        .collect();
+
+    for (chromosome, niche_count) in chromosome_pool.iter_mut().zip(niche_counts) {
+
+        let fitness = chromosome.get_fitness();
+
+        // Crowding has to push negative fitness further down rather than towards zero.
+        let shared_fitness = if fitness >= 0.0 { fitness / niche_count } else { fitness * niche_count };
+
+        chromosome.set_fitness(shared_fitness);
+
+    }
+
+}
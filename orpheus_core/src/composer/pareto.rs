@@ -0,0 +1,212 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::composer::chromosome::{Chromosome, fitness::FitnessBreakdown};
+
+// Fitness term optimised on its own in a Pareto front.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Objective {
+    ChordMelody,
+    BoundaryAlignment,
+    ChordEntropy,
+    DurationEntropy,
+    Rating,
+}
+
+impl Objective {
+
+    pub const FITNESS_TERMS: [Objective; 4] = [Objective::ChordMelody, Objective::BoundaryAlignment, Objective::ChordEntropy, Objective::DurationEntropy];
+
+    // Unweighted value of the term, higher being better.
+    pub fn value(&self, breakdown: &FitnessBreakdown) -> f64 {
+        match self {
+            Objective::ChordMelody => breakdown.chord_melody,
+            Objective::BoundaryAlignment => breakdown.boundary_alignment,
+            Objective::ChordEntropy => breakdown.chord_entropy,
+            Objective::DurationEntropy => breakdown.duration_entropy,
+            Objective::Rating => breakdown.rating,
+        }
+    }
+}
+
+// How the genetic algorithm decides which progressions survive.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Optimisation {
+    // A single fitness, the weighted sum of every term.
+    #[default]
+    WeightedSum,
+    // NSGA-II over the objectives, keeping the progressions no other progression beats on every objective.
+    // Parents are chosen by crowded tournament and survivors by non-dominated rank, so the configured selection,
+    // elites and fitness sharing are not used. Fitness is still the weighted sum, for stats and stopping.
+    Nsga2 { objectives: Vec<Objective> },
+}
+
+impl Optimisation {
+
+    // Objectives the Pareto front is measured over, every fitness term for a weighted sum.
+    pub fn objectives(&self) -> &[Objective] {
+        match self {
+            Optimisation::WeightedSum => &Objective::FITNESS_TERMS,
+            Optimisation::Nsga2 { objectives } => objectives,
+        }
+    }
+}
+
+// A non-dominated progression and its value for every objective, for offering trade-offs
+// such as the most consonant against the most varied.
+#[derive(Debug, Clone)]
+pub struct ParetoCandidate {
+    pub chromosome: Chromosome,
+    pub objectives: Vec<(Objective, f64)>,
+}
+
+impl ParetoCandidate {
+
+    pub fn value(&self, objective: Objective) -> Option<f64> {
+        self.objectives.iter().find(|(candidate_objective, _)| *candidate_objective == objective).map(|&(_, value)| value)
+    }
+}
+
+// Whether `one` is at least as good as `two` on every objective and better on at least one.
+pub fn dominates(one: &[f64], two: &[f64]) -> bool {
+    one.iter().zip(two).all(|(value_one, value_two)| value_one >= value_two)
+        && one.iter().zip(two).any(|(value_one, value_two)| value_one > value_two)
+}
+
+// Indices of the points in each non-dominated front, best front first.
+pub fn non_dominated_fronts(points: &[Vec<f64>]) -> Vec<Vec<usize>> {
+
+    let mut dominated_by_count = vec![0; points.len()];
+    let mut dominates_indices = vec![Vec::<usize>::new(); points.len()];
+
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            if dominates(&points[i], &points[j]) {
+                dominates_indices[i].push(j);
+                dominated_by_count[j] += 1;
+            }
+            else if dominates(&points[j], &points[i]) {
+                dominates_indices[j].push(i);
+                dominated_by_count[i] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::<Vec<usize>>::new();
+    let mut front: Vec<usize> = (0..points.len()).filter(|&index| dominated_by_count[index] == 0).collect();
+
+    while !front.is_empty() {
+
+        let mut next_front = Vec::<usize>::new();
+
+        for &index in &front {
+            for &dominated_index in &dominates_indices[index] {
+                dominated_by_count[dominated_index] -= 1;
+                if dominated_by_count[dominated_index] == 0 {
+                    next_front.push(dominated_index);
+                }
+            }
+        }
+
+        fronts.push(front);
+        front = next_front;
+
+    }
+
+    fronts
+
+}
+
+// Crowding distance of every point in the front, in the same order.
+// Points at either end of any objective are infinitely far from the crowd so they are always kept.
+pub fn crowding_distances(front: &[usize], points: &[Vec<f64>]) -> Vec<f64> {
+
+    let mut distances = vec![0.0; front.len()];
+    let objective_count = front.first().map_or(0, |&index| points[index].len());
+
+    let objective_values = (0..objective_count).map(|objective| front.iter().map(|&index| points[index][objective]).collect::<Vec<f64>>());
+
+    for values in objective_values {
+
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|&one, &two| values[one].total_cmp(&values[two]));
+
+        let minimum = values[order[0]];
+        let maximum = values[order[order.len() - 1]];
+
+        distances[order[0]] = f64::INFINITY;
+        distances[order[order.len() - 1]] = f64::INFINITY;
+
+        if maximum <= minimum {
+            continue;
+        }
+
+        for position in 1..order.len().saturating_sub(1) {
+            let gap = values[order[position + 1]] - values[order[position - 1]];
+            distances[order[position]] += gap / (maximum - minimum);
+        }
+
+    }
+
+    distances
+
+}
+
+// Non-dominated rank and crowding distance of every point.
+pub fn rank_and_crowding(points: &[Vec<f64>]) -> (Vec<usize>, Vec<f64>) {
+
+    let mut ranks = vec![0; points.len()];
+    let mut crowding = vec![0.0; points.len()];
+
+    for (rank, front) in non_dominated_fronts(points).into_iter().enumerate() {
+        for (&index, distance) in front.iter().zip(crowding_distances(&front, points)) {
+            ranks[index] = rank;
+            crowding[index] = distance;
+        }
+    }
+
+    (ranks, crowding)
+
+}
+
+// Indices of `quantity` points, filling with whole fronts then the least crowded of the front that doesn't fit.
+pub fn select_survivors(points: &[Vec<f64>], quantity: usize) -> Vec<usize> {
+
+    let mut survivors = Vec::<usize>::new();
+
+    for front in non_dominated_fronts(points) {
+
+        if survivors.len() + front.len() <= quantity {
+            survivors.extend(front);
+            continue;
+        }
+
+        let distances = crowding_distances(&front, points);
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|&one, &two| distances[two].total_cmp(&distances[one]));
+
+        survivors.extend(order.into_iter().take(quantity - survivors.len()).map(|position| front[position]));
+        break;
+
+    }
+
+    survivors
+
+}
+
+// Indices of `quantity` parents, each the winner of a binary tournament:
+// the lower rank wins, then the larger crowding distance.
+pub fn crowded_tournament<R: Rng + ?Sized>(ranks: &[usize], crowding: &[f64], quantity: usize, rng: &mut R) -> Vec<usize> {
+
+    (0..quantity)
+        .map(|_| {
+            let (one, two) = (rng.random_range(0..ranks.len()), rng.random_range(0..ranks.len()));
+
+            if ranks[one] != ranks[two] {
+                if ranks[one] < ranks[two] { one } else { two }
+            }
+            else if crowding[one] >= crowding[two] { one } else { two }
+        })
+        .collect()
+
+}
@@ -0,0 +1,69 @@
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+
+use crate::composer::chromosome::Chromosome;
+
+// Snapshot of the pool at the start of a generation, after fitness has been calculated.
+#[derive(Debug, Clone)]
+pub struct GenerationStats {
+    pub generation: usize,
+    pub max_generations: usize,
+    pub best_fitness: f64,
+    pub mean_fitness: f64,
+    pub worst_fitness: f64,
+    // Mean chord distance across the pool, only measured when the stopping criteria or mutation schedule use it
+    // or the stats are asked for directly, as it compares every pair.
+    pub diversity: Option<f64>,
+    // Scale the mutation schedule applies to the configured mutation rates this generation.
+    pub mutation_scale: f64,
+    pub best_chromosome: Chromosome,
+}
+
+impl GenerationStats {
+
+    pub fn new(generation: usize, max_generations: usize, chromosome_pool: &[Chromosome], diversity: Option<f64>, mutation_scale: f64) -> GenerationStats {
+
+        let best_chromosome = chromosome_pool.iter()
+            .max_by(|chromosome_one, chromosome_two| chromosome_one.get_fitness().total_cmp(&chromosome_two.get_fitness()))
+            .expect("chromosome pool is not empty");
+
+        let total_fitness: f64 = chromosome_pool.iter().map(|chromosome| chromosome.get_fitness()).sum();
+
+        GenerationStats {
+            generation,
+            max_generations,
+            best_fitness: best_chromosome.get_fitness(),
+            mean_fitness: total_fitness / chromosome_pool.len() as f64,
+            worst_fitness: chromosome_pool.iter().map(|chromosome| chromosome.get_fitness()).fold(f64::INFINITY, f64::min),
+            diversity,
+            mutation_scale,
+            best_chromosome: best_chromosome.clone(),
+        }
+
+    }
+}
+
+// Returned by a progress observer to decide whether the run carries on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    Stop,
+}
+
+// Shared flag that stops a compose run from another thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
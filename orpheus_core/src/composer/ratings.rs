@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::composer::{chromosome::Chromosome, results::ChordDistance};
+use crate::theory::Chord;
+
+// Strongest like and dislike a rating can give.
+pub const LIKE: f64 = 1.0;
+pub const DISLIKE: f64 = -1.0;
+
+// A user's opinion of a progression, or of part of one, fed back into fitness.
+// Chromosomes playing the same chords over the rated time range gain or lose fitness in proportion to how much they share.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    // Semiquaver the rated chords start on.
+    pub start: u16,
+    // Chord playing on every semiquaver of the rated range.
+    pub chord_timeline: Vec<Chord>,
+    // From DISLIKE to LIKE.
+    pub score: f64,
+}
+
+impl Rating {
+
+    pub fn new(chromosome: &Chromosome, score: f64) -> Rating {
+        Rating::for_segment(chromosome, 0..chromosome.get_duration(), score)
+    }
+
+    // Rates only the chords playing in `segment`, such as a chorus the user likes.
+    pub fn for_segment(chromosome: &Chromosome, segment: Range<u16>, score: f64) -> Rating {
+
+        let chord_timeline = ChordDistance::Timeline.profile(chromosome);
+        let end = (segment.end as usize).min(chord_timeline.len());
+        let start = (segment.start as usize).min(end);
+
+        Rating {
+            start: start as u16,
+            chord_timeline: chord_timeline[start..end].to_vec(),
+            score: score.clamp(DISLIKE, LIKE),
+        }
+
+    }
+
+    // Fraction of the rated range where the timeline plays the rated chords.
+    pub fn similarity(&self, chord_timeline: &[Chord]) -> f64 {
+
+        if self.chord_timeline.is_empty() {
+            return 0.0;
+        }
+
+        let matching_semiquavers = chord_timeline.iter()
+            .skip(self.start as usize)
+            .zip(&self.chord_timeline)
+            .filter(|(chord, rated_chord)| chord == rated_chord)
+            .count();
+
+        matching_semiquavers as f64 / self.chord_timeline.len() as f64
+
+    }
+}
+
+// Mean of every rating's score weighted by the chromosome's similarity to it, from -1 to 1.
+pub fn rating_fitness(ratings: &[Rating], chromosome: &Chromosome) -> f64 {
+
+    if ratings.is_empty() {
+        return 0.0;
+    }
+
+    let chord_timeline = ChordDistance::Timeline.profile(chromosome);
+
+    let fitness: f64 = ratings.iter()
+        .map(|rating| rating.score * rating.similarity(&chord_timeline))
+        .sum();
+
+    fitness / ratings.len() as f64
+
+}
+
+// Adds weighted rating fitness on top of the fitness already calculated for every chromosome in the pool.
+pub fn apply_ratings(ratings: &[Rating], chromosome_pool: &mut [Chromosome], weight: f64) {
+
+    if ratings.is_empty() {
+        return;
+    }
+
+    for chromosome in chromosome_pool {
+        chromosome.set_fitness(chromosome.get_fitness() + weight * rating_fitness(ratings, chromosome));
+    }
+
+}
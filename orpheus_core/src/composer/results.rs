@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+
+use crate::composer::{self, chromosome::Chromosome};
+use crate::theory::{Chord, Key};
+
+// Distances from the best result below which a result is labelled safe or balanced.
+const SAFE_DISTANCE: f64 = 0.25;
+const BALANCED_DISTANCE: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ChordDistance {
+    // Fraction of the melody's semiquavers where the two progressions play different chords.
+    #[default]
+    Timeline,
+    // Edit distance between the two chord sequences ignoring durations, as a fraction of the longer sequence.
+    Sequence,
+}
+
+impl ChordDistance {
+
+    // Distance between two progressions, from 0 (identical) to 1 (nothing in common).
+    pub fn distance(&self, chromosome_one: &Chromosome, chromosome_two: &Chromosome) -> f64 {
+        self.profile_distance(&self.profile(chromosome_one), &self.profile(chromosome_two))
+    }
+
+    // Chords the distance is measured over, so they can be worked out once when comparing a whole pool.
+    pub fn profile(&self, chromosome: &Chromosome) -> Vec<Chord> {
+        match self {
+            ChordDistance::Timeline => chord_timeline(chromosome),
+            ChordDistance::Sequence => chromosome.get_genes().iter().map(|gene| gene.chord()).collect(),
+        }
+    }
+
+    pub fn profile_distance(&self, profile_one: &[Chord], profile_two: &[Chord]) -> f64 {
+        match self {
+            ChordDistance::Timeline => timeline_distance(profile_one, profile_two),
+            ChordDistance::Sequence => sequence_distance(profile_one, profile_two),
+        }
+    }
+
+    // Distance between every pair of chromosomes in the pool.
+    pub fn pairwise_distances(&self, chromosome_pool: &[Chromosome]) -> Vec<Vec<f64>> {
+
+        let profiles: Vec<Vec<Chord>> = chromosome_pool.iter().map(|chromosome| self.profile(chromosome)).collect();
+        let mut distances = vec![vec![0.0; profiles.len()]; profiles.len()];
+
+        for i in 0..profiles.len() {
+            for j in (i + 1)..profiles.len() {
+                let distance = self.profile_distance(&profiles[i], &profiles[j]);
+                distances[i][j] = distance;
+                distances[j][i] = distance;
+            }
+        }
+
+        distances
+
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Character {
+    Safe,
+    Balanced,
+    Adventurous,
+}
+
+impl Character {
+
+    pub fn from_distance(distance_from_best: f64) -> Character {
+        if distance_from_best < SAFE_DISTANCE {
+            Character::Safe
+        }
+        else if distance_from_best < BALANCED_DISTANCE {
+            Character::Balanced
+        }
+        else {
+            Character::Adventurous
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub chromosome: Chromosome,
+    // Distance from the fittest candidate.
+    pub distance_from_best: f64,
+    // Distance from the closest other candidate.
+    pub distance_from_nearest: f64,
+    pub character: Character,
+}
+
+impl Candidate {
+
+    pub fn print(&self, key: &Key) {
+        let (distance_from_best, character) = (self.distance_from_best, self.character);
+
+        self.chromosome.print(key);
+        println!("DISTANCE FROM BEST: {distance_from_best:.2} ({character:?})");
+    }
+}
+
+// Picks up to `quantity` high fitness chromosomes that are all at least `minimum_distance` apart.
+// If the pool is too uniform for that, the rest are filled with the fittest distinct chromosomes left.
+pub fn select_diverse_results(chromosome_pool: &[Chromosome], quantity: usize, minimum_distance: f64, chord_distance: ChordDistance) -> Vec::<Candidate> {
+
+    let mut sorted_chromosome_pool: Vec<&Chromosome> = chromosome_pool.iter().collect();
+    sorted_chromosome_pool.sort_by(|chromosome_one, chromosome_two| composer::compare_by_fitness_descending(chromosome_one, chromosome_two));
+
+    let mut results = Vec::<&Chromosome>::new();
+
+    for threshold in [minimum_distance, f64::MIN_POSITIVE] {
+        for &chromosome in &sorted_chromosome_pool {
+
+            if results.len() >= quantity {
+                break;
+            }
+
+            if results.iter().all(|result| chord_distance.distance(result, chromosome) >= threshold) {
+                results.push(chromosome);
+            }
+
+        }
+    }
+
+    results.iter()
+        .enumerate()
+        .map(|(index, &chromosome)| {
+            let distance_from_best = chord_distance.distance(results[0], chromosome);
+            let distance_from_nearest = results.iter()
+                .enumerate()
+                .filter(|&(other_index, _)| other_index != index)
+                .map(|(_, other)| chord_distance.distance(other, chromosome))
+                .fold(f64::INFINITY, f64::min);
+
+            Candidate {
+                chromosome: chromosome.clone(),
+                distance_from_best,
+                distance_from_nearest: if distance_from_nearest.is_finite() { distance_from_nearest } else { 0.0 },
+                character: Character::from_distance(distance_from_best),
+            }
+        })
+        .collect()
+
+}
+
+// Chord playing on every semiquaver of the progression.
+fn chord_timeline(chromosome: &Chromosome) -> Vec<Chord> {
+    chromosome.get_genes()
+        .iter()
+        .flat_map(|gene| std::iter::repeat_n(gene.chord(), gene.get_duration() as usize))
+        .collect()
+}
+
+fn timeline_distance(timeline_one: &[Chord], timeline_two: &[Chord]) -> f64 {
+
+    let duration = timeline_one.len().max(timeline_two.len());
+
+    if duration == 0 {
+        return 0.0;
+    }
+
+    let matching_semiquavers = timeline_one.iter().zip(timeline_two).filter(|(chord_one, chord_two)| chord_one == chord_two).count();
+
+    (duration - matching_semiquavers) as f64 / duration as f64
+
+}
+
+fn sequence_distance(chords_one: &[Chord], chords_two: &[Chord]) -> f64 {
+
+    let longest_sequence = chords_one.len().max(chords_two.len());
+
+    if longest_sequence == 0 {
+        return 0.0;
+    }
+
+    // Levenshtein distance, keeping only the previous row of the table.
+    let mut previous_row: Vec<usize> = (0..=chords_two.len()).collect();
+
+    for (i, chord_one) in chords_one.iter().enumerate() {
+
+        let mut current_row = vec![i + 1; chords_two.len() + 1];
+
+        for (j, chord_two) in chords_two.iter().enumerate() {
+            let substitution_cost = if chord_one == chord_two { 0 } else { 1 };
+            current_row[j + 1] = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+
+        previous_row = current_row;
+
+    }
+
+    previous_row[chords_two.len()] as f64 / longest_sequence as f64
+
+}
@@ -0,0 +1,264 @@
+use rand::{Rng, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::composer::{
+    self,
+    BAR_DURATION,
+    chromosome::{self, Chromosome, gene::{self, Gene, MAX_DURATION}},
+    config::Config,
+    harmonizer,
+};
+use crate::theory::{Chord, ChordQuality, Interval, Key};
+
+// Chords each template's harmonic rhythm is tried with, in semiquavers.
+const TEMPLATE_CHORD_DURATIONS: [u16; 3] = [BAR_DURATION, BAR_DURATION / 2, BAR_DURATION * 2];
+
+const SEEDED_RATIO: f64 = 0.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProgressionTemplate {
+    // I–V–vi–IV, or i–VI–III–VII in a minor key.
+    OneFiveSixFour,
+    // I–I–I–I–IV–IV–I–I–V–IV–I–I, or the same with minor tonic and subdominant in a minor key.
+    TwelveBarBlues,
+}
+
+impl ProgressionTemplate {
+
+    // One chord per step of the template, as (semitones above the tonic, quality).
+    fn chords(&self, key: Key) -> Vec<(u16, ChordQuality)> {
+
+        match (self, key.quality) {
+            (ProgressionTemplate::OneFiveSixFour, ChordQuality::Major) => vec![
+                (0, ChordQuality::Major), (7, ChordQuality::Major), (9, ChordQuality::Minor), (5, ChordQuality::Major),
+            ],
+            (ProgressionTemplate::OneFiveSixFour, ChordQuality::Minor) => vec![
+                (0, ChordQuality::Minor), (8, ChordQuality::Major), (3, ChordQuality::Major), (10, ChordQuality::Major),
+            ],
+            (ProgressionTemplate::TwelveBarBlues, _) => [0, 0, 0, 0, 5, 5, 0, 0, 7, 5, 0, 0]
+                .into_iter()
+                .map(|degree| if degree == 7 { (degree, ChordQuality::Major) } else { (degree, key.quality) })
+                .collect(),
+        }
+
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeedingConfig {
+    // Fraction of the initial pool built from the sources below, with the rest random.
+    pub ratio: f64,
+    // Whether random progressions using only the chords of the melody's key fill the seeded share.
+    pub diatonic: bool,
+    pub templates: Vec<ProgressionTemplate>,
+    // Progressions typed in by the user, such as "C G Am F" or "Dm:8 G:8 C", repeated to the melody's length.
+    // Each chord lasts a bar unless given a duration in semiquavers after a colon.
+    pub progressions: Vec<String>,
+}
+
+impl Default for SeedingConfig {
+    fn default() -> Self {
+        SeedingConfig {
+            ratio: SEEDED_RATIO,
+            diatonic: true,
+            templates: vec![ProgressionTemplate::OneFiveSixFour, ProgressionTemplate::TwelveBarBlues],
+            progressions: Vec::new(),
+        }
+    }
+}
+
+impl SeedingConfig {
+
+    // Checks every user progression parses, as part of Config::validate.
+    pub fn validate(&self) -> Result<(), ProgressionError> {
+        self.progressions.iter().try_for_each(|progression| parse_progression(progression).map(|_| ()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressionError {
+    Empty,
+    InvalidChord(String),
+    InvalidDuration(String),
+}
+
+impl fmt::Display for ProgressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProgressionError::Empty => write!(f, "progression has no chords"),
+            ProgressionError::InvalidChord(chord) => write!(f, "{chord:?} is not a major or minor chord"),
+            ProgressionError::InvalidDuration(chord) => write!(f, "{chord:?} does not have a duration in 1..={MAX_DURATION}"),
+        }
+    }
+}
+
+impl std::error::Error for ProgressionError {}
+
+// Key whose scale covers the most of the melody by duration, with ties broken by time spent on the tonic
+// and then in favour of major keys.
+pub fn detect_key(melody: &[(u16, u16)]) -> Key {
+
+    let mut pitch_class_durations = [0u32; 12];
+
+    for note in melody {
+        pitch_class_durations[(note.0 % 12) as usize] += note.1 as u32;
+    }
+
+    let keys: Vec<Key> = Key::all().collect();
+
+    *keys.iter()
+        .rev()
+        .max_by_key(|key| {
+            let scale_duration: u32 = key.scale().pitch_classes().iter().map(|pitch_class| pitch_class_durations[pitch_class.get() as usize]).sum();
+
+            (scale_duration, pitch_class_durations[key.tonic.get() as usize])
+        })
+        .expect("there are 24 keys")
+
+}
+
+// Parses chord symbols separated by spaces or bar lines, such as "C | G | Am | F" or "F#m:8 Bb:8".
+pub fn parse_progression(progression: &str) -> Result<Vec<Gene>, ProgressionError> {
+
+    let genes = progression
+        .split(|character: char| character.is_whitespace() || character == '|')
+        .filter(|symbol| !symbol.is_empty())
+        .map(parse_chord_symbol)
+        .collect::<Result<Vec<Gene>, ProgressionError>>()?;
+
+    if genes.is_empty() {
+        return Err(ProgressionError::Empty);
+    }
+
+    Ok(genes)
+
+}
+
+fn parse_chord_symbol(symbol: &str) -> Result<Gene, ProgressionError> {
+
+    let (chord, duration) = match symbol.split_once(':') {
+        Some((chord, duration)) => (chord, duration.parse::<u16>().map_err(|_| ProgressionError::InvalidDuration(symbol.to_string()))?),
+        None => (symbol, BAR_DURATION),
+    };
+
+    let invalid_chord = || ProgressionError::InvalidChord(symbol.to_string());
+
+    let mut characters = chord.chars();
+    let letter_root_note = match characters.next().ok_or_else(invalid_chord)? {
+        'C' => 0, 'D' => 2, 'E' => 4, 'F' => 5, 'G' => 7, 'A' => 9, 'B' => 11,
+        _ => return Err(invalid_chord()),
+    };
+
+    let rest = characters.as_str();
+    let (root_note, quality) = match rest {
+        "" => (letter_root_note, 0),
+        "m" => (letter_root_note, 1),
+        "#" | "♯" => (letter_root_note + 1, 0),
+        "#m" | "♯m" => (letter_root_note + 1, 1),
+        "b" | "♭" => (letter_root_note + 11, 0),
+        "bm" | "♭m" => (letter_root_note + 11, 1),
+        _ => return Err(invalid_chord()),
+    };
+
+    gene::generate_gene(root_note % 12, quality, duration).map_err(|_| ProgressionError::InvalidDuration(symbol.to_string()))
+
+}
+
+// Builds the initial pool: the harmonizer's optimum if enabled, then the configured share of seeded progressions,
+// then random chromosomes. Seeded progressions come from user progressions, templates at each harmonic rhythm,
+// and finally random diatonic progressions.
+pub fn generate_seeded_chromosome_pool<R: Rng + ?Sized>(rng: &mut R, config: &Config, melody: &[(u16, u16)]) -> Vec::<Chromosome> {
+
+    let melody_duration = composer::calculate_melody_duration(melody);
+    let seeding = &config.seeding;
+    let seeded_size = ((config.initial_pool_size as f64 * seeding.ratio.clamp(0.0, 1.0)).round() as usize).min(config.initial_pool_size);
+
+    let key = config.key_for(melody);
+    let mut chromosome_pool = Vec::<Chromosome>::new();
+
+    if config.seed_with_harmonization && let Some(harmonization) = harmonizer::harmonize(melody, &key, &config.fitness_weights) {
+        chromosome_pool.push(harmonization.chromosome);
+    }
+
+    if seeded_size > 0 {
+
+        // Progressions that don't parse are rejected by Config::validate, and skipped if it wasn't called.
+        let progressions = seeding.progressions.iter().filter_map(|progression| parse_progression(progression).ok());
+        let templates = seeding.templates.iter().flat_map(|template| {
+            TEMPLATE_CHORD_DURATIONS.iter().map(move |&chord_duration| template_genes(*template, key, chord_duration))
+        });
+
+        for genes in progressions.chain(templates) {
+            if chromosome_pool.len() >= seeded_size {
+                break;
+            }
+            chromosome_pool.push(repeat_to_duration(&genes, melody_duration));
+        }
+
+        if seeding.diatonic {
+            while chromosome_pool.len() < seeded_size {
+                chromosome_pool.push(generate_diatonic_chromosome(rng, key, melody_duration));
+            }
+        }
+
+    }
+
+    composer::refill_chromosome_pool(rng, &mut chromosome_pool, config.initial_pool_size, melody_duration);
+
+    chromosome_pool
+
+}
+
+// Random chromosome whose chords all belong to the key.
+pub fn generate_diatonic_chromosome<R: Rng + ?Sized>(rng: &mut R, key: Key, melody_duration: u16) -> Chromosome {
+
+    let diatonic_chords = key.diatonic_chords();
+    let mut genes = chromosome::generate_random_chromosome(rng, melody_duration).get_genes().to_vec();
+
+    for gene in &mut genes {
+        let &chord = diatonic_chords.choose(rng).expect("every key has diatonic chords");
+        *gene = Gene::from_chord(chord, gene.get_duration()).expect("diatonic chord keeps the gene's duration");
+    }
+
+    chromosome::generate_chromosome(genes)
+
+}
+
+fn template_genes(template: ProgressionTemplate, key: Key, chord_duration: u16) -> Vec<Gene> {
+    template.chords(key)
+        .into_iter()
+        .map(|(degree, quality)| {
+            Gene::from_chord(Chord::new(key.tonic + Interval::new(degree), quality), chord_duration.min(MAX_DURATION)).expect("template chord duration is in range")
+        })
+        .collect()
+}
+
+// Loops the genes until they cover the melody, cutting the final gene short.
+fn repeat_to_duration(genes: &[Gene], melody_duration: u16) -> Chromosome {
+
+    let mut repeated_genes = Vec::<Gene>::new();
+    let mut duration = 0;
+
+    for gene in genes.iter().cycle() {
+
+        if duration >= melody_duration {
+            break;
+        }
+
+        let remaining_duration = melody_duration - duration;
+        let gene = if gene.get_duration() > remaining_duration {
+            gene.with_duration(remaining_duration).expect("remaining duration is shorter than a valid gene duration")
+        } else {
+            *gene
+        };
+
+        duration += gene.get_duration();
+        repeated_genes.push(gene);
+
+    }
+
+    chromosome::generate_chromosome(repeated_genes)
+
+}
@@ -0,0 +1,125 @@
+use rand::{Rng, distr::{Distribution, weighted::WeightedIndex}};
+use serde::{Deserialize, Serialize};
+
+use crate::composer::{self, chromosome::Chromosome};
+
+// Weight the least fit chromosome gets on a fitness proportionate wheel, on the same scale as fitness.
+const MINIMUM_SELECTION_WEIGHT: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Selection {
+    // The fittest chromosomes, with no randomness.
+    Truncation,
+    // The fittest of `size` chromosomes drawn at random, repeated for every pick.
+    Tournament { size: usize },
+    // Roulette wheel, with chances proportional to fitness.
+    FitnessProportionate,
+    // Roulette wheel, with chances proportional to position in the fitness ranking.
+    Rank,
+    // Evenly spaced pointers on a fitness proportionate wheel, spun once.
+    StochasticUniversal,
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Selection::Tournament { size: 3 }
+    }
+}
+
+impl Selection {
+
+    pub fn select<R: Rng + ?Sized>(&self, chromosome_pool: &[Chromosome], quantity: usize, rng: &mut R) -> Vec::<Chromosome> {
+
+        if chromosome_pool.is_empty() || quantity == 0 {
+            return Vec::<Chromosome>::new();
+        }
+
+        match self {
+            Selection::Truncation => composer::select_top_n_pool(chromosome_pool, quantity),
+            Selection::Tournament { size } => select_by_tournament(chromosome_pool, quantity, *size, rng),
+            Selection::FitnessProportionate => select_by_roulette(chromosome_pool, quantity, &fitness_weights(chromosome_pool), rng),
+            Selection::Rank => select_by_roulette(chromosome_pool, quantity, &rank_weights(chromosome_pool), rng),
+            Selection::StochasticUniversal => select_by_universal_sampling(chromosome_pool, quantity, &fitness_weights(chromosome_pool), rng),
+        }
+
+    }
+}
+
+fn select_by_tournament<R: Rng + ?Sized>(chromosome_pool: &[Chromosome], quantity: usize, tournament_size: usize, rng: &mut R) -> Vec::<Chromosome> {
+
+    (0..quantity)
+        .map(|_| {
+            (0..tournament_size.max(1))
+                .map(|_| &chromosome_pool[rng.random_range(0..chromosome_pool.len())])
+                .max_by(|chromosome_one, chromosome_two| chromosome_one.get_fitness().total_cmp(&chromosome_two.get_fitness()))
+                .expect("tournament has at least one entrant")
+                .clone()
+        })
+        .collect()
+
+}
+
+fn select_by_roulette<R: Rng + ?Sized>(chromosome_pool: &[Chromosome], quantity: usize, weights: &[f64], rng: &mut R) -> Vec::<Chromosome> {
+
+    let distribution = WeightedIndex::new(weights).expect("selection weights are positive");
+
+    (0..quantity)
+        .map(|_| chromosome_pool[distribution.sample(rng)].clone())
+        .collect()
+
+}
+
+fn select_by_universal_sampling<R: Rng + ?Sized>(chromosome_pool: &[Chromosome], quantity: usize, weights: &[f64], rng: &mut R) -> Vec::<Chromosome> {
+
+    let total_weight: f64 = weights.iter().sum();
+    let pointer_spacing = total_weight / quantity as f64;
+    let mut pointer = rng.random_range(0.0..pointer_spacing);
+
+    let mut selection = Vec::<Chromosome>::new();
+    let mut cumulative_weight = 0.0;
+
+    for (chromosome, weight) in chromosome_pool.iter().zip(weights) {
+
+        cumulative_weight += weight;
+
+        while pointer < cumulative_weight && selection.len() < quantity {
+            selection.push(chromosome.clone());
+            pointer += pointer_spacing;
+        }
+
+    }
+
+    // Rounding can leave the final pointer just past the end of the wheel.
+    while selection.len() < quantity {
+        selection.push(chromosome_pool[chromosome_pool.len() - 1].clone());
+    }
+
+    selection
+
+}
+
+// Fitness can be negative, so shift every fitness so the least fit chromosome still has a small chance.
+fn fitness_weights(chromosome_pool: &[Chromosome]) -> Vec<f64> {
+
+    let minimum_fitness = chromosome_pool.iter().map(|chromosome| chromosome.get_fitness()).fold(f64::INFINITY, f64::min);
+
+    chromosome_pool.iter()
+        .map(|chromosome| chromosome.get_fitness() - minimum_fitness + MINIMUM_SELECTION_WEIGHT)
+        .collect()
+
+}
+
+// Weight of each chromosome is its rank, from 1 for the least fit up to the pool size for the fittest.
+fn rank_weights(chromosome_pool: &[Chromosome]) -> Vec<f64> {
+
+    let mut ranked_indices: Vec<usize> = (0..chromosome_pool.len()).collect();
+    ranked_indices.sort_by(|&index_one, &index_two| chromosome_pool[index_one].get_fitness().total_cmp(&chromosome_pool[index_two].get_fitness()));
+
+    let mut weights = vec![0.0; chromosome_pool.len()];
+    for (rank, index) in ranked_indices.into_iter().enumerate() {
+        weights[index] = (rank + 1) as f64;
+    }
+
+    weights
+
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+const MAX_GENERATIONS: usize = 1000;
+const STAGNATION_GENERATIONS: usize = 150;
+
+// Conditions that end a compose run early. The generation limit always applies,
+// every other criterion is off when None.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StoppingCriteria {
+    pub max_generations: usize,
+    // Generations in a row without the best fitness improving.
+    pub stagnation_generations: Option<usize>,
+    // Total fitness, on the scale set by the fitness weights.
+    pub target_fitness: Option<f64>,
+    pub time_budget: Option<Duration>,
+    // Mean chord distance between chromosomes in the pool, from 0 to 1.
+    pub minimum_diversity: Option<f64>,
+}
+
+impl Default for StoppingCriteria {
+    fn default() -> Self {
+        StoppingCriteria {
+            max_generations: MAX_GENERATIONS,
+            stagnation_generations: Some(STAGNATION_GENERATIONS),
+            target_fitness: None,
+            time_budget: None,
+            minimum_diversity: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    GenerationLimit,
+    Stagnation,
+    TargetFitness,
+    TimeBudget,
+    LowDiversity,
+    ObserverStopped,
+    Cancelled,
+}
+
+// Tracks a run's progress against its stopping criteria.
+#[derive(Debug, Clone)]
+pub struct StoppingMonitor {
+    criteria: StoppingCriteria,
+    start_generation: usize,
+    start_time: Instant,
+    best_fitness: Option<f64>,
+    last_improvement_generation: usize,
+}
+
+impl StoppingMonitor {
+
+    // Generation counts are measured from `start_generation`, the generation the run begins at.
+    pub fn new(criteria: &StoppingCriteria, start_generation: usize) -> StoppingMonitor {
+        StoppingMonitor {
+            criteria: criteria.clone(),
+            start_generation,
+            start_time: Instant::now(),
+            best_fitness: None,
+            last_improvement_generation: start_generation,
+        }
+    }
+
+    // Records the state of the pool at the start of a generation and returns why the run should stop, if it should.
+    // Diversity only needs measuring when there is a minimum diversity.
+    pub fn check(&mut self, generation: usize, best_fitness: f64, diversity: Option<f64>) -> Option<StopReason> {
+
+        if self.best_fitness.is_none_or(|previous_best_fitness| best_fitness > previous_best_fitness) {
+            self.best_fitness = Some(best_fitness);
+            self.last_improvement_generation = generation;
+        }
+
+        if self.criteria.target_fitness.is_some_and(|target_fitness| best_fitness >= target_fitness) {
+            return Some(StopReason::TargetFitness);
+        }
+
+        if generation - self.start_generation >= self.criteria.max_generations {
+            return Some(StopReason::GenerationLimit);
+        }
+
+        if self.criteria.stagnation_generations.is_some_and(|stagnation_generations| generation - self.last_improvement_generation >= stagnation_generations) {
+            return Some(StopReason::Stagnation);
+        }
+
+        if let (Some(minimum_diversity), Some(diversity)) = (self.criteria.minimum_diversity, diversity) && diversity < minimum_diversity {
+            return Some(StopReason::LowDiversity);
+        }
+
+        if self.criteria.time_budget.is_some_and(|time_budget| self.start_time.elapsed() >= time_budget) {
+            return Some(StopReason::TimeBudget);
+        }
+
+        None
+
+    }
+}
@@ -0,0 +1,291 @@
+// C interface for the iOS app. Memory returned to the caller is owned by Rust
+// and must be handed back to the matching orpheus_*_free function.
+
+use std::{ffi::{CString, c_char, c_void}, ptr, slice};
+
+use crate::composer::{
+    self,
+    chromosome::{self, Chromosome, fitness::FitnessWeights, gene},
+    config::Config,
+    progress::{CancellationToken, Control, GenerationStats},
+    seeding,
+    stopping::StopReason,
+};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct OrpheusNote {
+    pub midi_note: u16,
+    // Semiquavers.
+    pub duration: u16,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct OrpheusChord {
+    pub root_note: u16,
+    // 0 for major, 1 for minor.
+    pub chord_type: u16,
+    // Semiquavers.
+    pub duration: u16,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct OrpheusProgress {
+    pub generation: usize,
+    pub max_generations: usize,
+    pub best_fitness: f64,
+    pub mean_fitness: f64,
+    pub worst_fitness: f64,
+    // NaN when the run doesn't measure diversity.
+    pub diversity: f64,
+    pub mutation_scale: f64,
+    // Only valid for the duration of the callback.
+    pub best_chords: *const OrpheusChord,
+    pub best_chord_count: usize,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct OrpheusCandidate {
+    pub chords: *mut OrpheusChord,
+    pub chord_count: usize,
+    pub fitness: f64,
+    pub distance_from_best: f64,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct OrpheusComposition {
+    pub candidates: *mut OrpheusCandidate,
+    pub candidate_count: usize,
+    pub stop_reason: u32,
+    pub generations: usize,
+}
+
+// Called every generation. Returning false stops the run.
+pub type OrpheusProgressCallback = Option<unsafe extern "C" fn(progress: *const OrpheusProgress, user_data: *mut c_void) -> bool>;
+
+#[unsafe(no_mangle)]
+pub extern "C" fn orpheus_cancellation_token_new() -> *mut CancellationToken {
+    Box::into_raw(Box::new(CancellationToken::new()))
+}
+
+/// # Safety
+/// `token` must be null or a pointer returned by `orpheus_cancellation_token_new` that has not been freed.
+/// It is safe to call from a different thread to the one running `orpheus_compose`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn orpheus_cancellation_token_cancel(token: *const CancellationToken) {
+    if let Some(token) = unsafe { token.as_ref() } {
+        token.cancel();
+    }
+}
+
+/// # Safety
+/// `token` must be null or a pointer returned by `orpheus_cancellation_token_new`,
+/// not already freed and not in use by a running `orpheus_compose`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn orpheus_cancellation_token_free(token: *mut CancellationToken) {
+    if !token.is_null() {
+        drop(unsafe { Box::from_raw(token) });
+    }
+}
+
+/// Runs the genetic algorithm on a melody with the default configuration.
+/// Returns null if the melody is empty, a note has no duration or the melody lasts longer than 65535 semiquavers.
+///
+/// # Safety
+/// `melody` must point to `melody_length` notes. `cancellation_token` must be null or a live token
+/// from `orpheus_cancellation_token_new`. `callback` is called on the calling thread with `user_data`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn orpheus_compose(
+    melody: *const OrpheusNote,
+    melody_length: usize,
+    callback: OrpheusProgressCallback,
+    user_data: *mut c_void,
+    cancellation_token: *const CancellationToken,
+) -> *mut OrpheusComposition {
+
+    if melody.is_null() || melody_length == 0 {
+        return ptr::null_mut();
+    }
+
+    let melody = to_melody(unsafe { slice::from_raw_parts(melody, melody_length) });
+    if !is_valid_melody(&melody) {
+        return ptr::null_mut();
+    }
+
+    let cancellation_token = unsafe { cancellation_token.as_ref() }.cloned().unwrap_or_default();
+
+    let observer = |stats: &GenerationStats| {
+        let Some(callback) = callback else {
+            return Control::Continue;
+        };
+
+        let best_chords = to_ffi_chords(&stats.best_chromosome);
+        let progress = OrpheusProgress {
+            generation: stats.generation,
+            max_generations: stats.max_generations,
+            best_fitness: stats.best_fitness,
+            mean_fitness: stats.mean_fitness,
+            worst_fitness: stats.worst_fitness,
+            diversity: stats.diversity.unwrap_or(f64::NAN),
+            mutation_scale: stats.mutation_scale,
+            best_chords: best_chords.as_ptr(),
+            best_chord_count: best_chords.len(),
+        };
+
+        if unsafe { callback(&progress, user_data) } { Control::Continue } else { Control::Stop }
+    };
+
+    let Ok(composition) = composer::compose_with_observer(&melody, &Config::default(), observer, &cancellation_token) else {
+        return ptr::null_mut();
+    };
+
+    let candidates: Vec<OrpheusCandidate> = composition.candidates
+        .iter()
+        .map(|candidate| {
+            let (chords, chord_count) = into_raw_parts(to_ffi_chords(&candidate.chromosome));
+
+            OrpheusCandidate {
+                chords,
+                chord_count,
+                fitness: candidate.chromosome.get_fitness(),
+                distance_from_best: candidate.distance_from_best,
+            }
+        })
+        .collect();
+
+    let (candidates, candidate_count) = into_raw_parts(candidates);
+
+    Box::into_raw(Box::new(OrpheusComposition {
+        candidates,
+        candidate_count,
+        stop_reason: stop_reason_code(composition.stop_reason),
+        generations: composition.generations,
+    }))
+
+}
+
+/// # Safety
+/// `composition` must be null or a pointer returned by `orpheus_compose` that has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn orpheus_composition_free(composition: *mut OrpheusComposition) {
+
+    if composition.is_null() {
+        return;
+    }
+
+    let composition = unsafe { Box::from_raw(composition) };
+    let candidates = unsafe { from_raw_parts(composition.candidates, composition.candidate_count) };
+
+    for candidate in candidates {
+        drop(unsafe { from_raw_parts(candidate.chords, candidate.chord_count) });
+    }
+
+}
+
+/// Explains the fitness of a progression against a melody as a JSON object, with each fitness term
+/// and how every melody note fits its chord in the key detected from the melody,
+/// so clashing notes can be highlighted.
+/// Returns null if either is empty, a note has no duration, the melody lasts longer than 65535 semiquavers,
+/// a chord is invalid or the progression doesn't last as long as the melody.
+///
+/// # Safety
+/// `melody` must point to `melody_length` notes and `chords` to `chord_count` chords.
+/// The returned string must be freed with `orpheus_string_free`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn orpheus_fitness_breakdown_json(
+    melody: *const OrpheusNote,
+    melody_length: usize,
+    chords: *const OrpheusChord,
+    chord_count: usize,
+) -> *mut c_char {
+
+    if melody.is_null() || melody_length == 0 || chords.is_null() || chord_count == 0 {
+        return ptr::null_mut();
+    }
+
+    let melody = to_melody(unsafe { slice::from_raw_parts(melody, melody_length) });
+    if !is_valid_melody(&melody) {
+        return ptr::null_mut();
+    }
+
+    let chords = unsafe { slice::from_raw_parts(chords, chord_count) };
+
+    // Summed before building the chromosome, whose own duration would overflow.
+    let chords_duration = chords.iter().try_fold(0u16, |duration, chord| duration.checked_add(chord.duration));
+    if chords_duration != Some(composer::calculate_melody_duration(&melody)) {
+        return ptr::null_mut();
+    }
+
+    let Ok(genes) = chords
+        .iter()
+        .map(|chord| gene::generate_gene(chord.root_note, chord.chord_type, chord.duration))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return ptr::null_mut();
+    };
+
+    let chromosome = chromosome::generate_chromosome(genes);
+
+    chromosome.fitness_breakdown(&melody, &seeding::detect_key(&melody), &FitnessWeights::default())
+        .to_json()
+        .ok()
+        .and_then(|json| CString::new(json).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+
+}
+
+/// # Safety
+/// `string` must be null or a string returned by this library that has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn orpheus_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+fn to_melody(notes: &[OrpheusNote]) -> Vec<(u16, u16)> {
+    notes.iter().map(|note| (note.midi_note, note.duration)).collect()
+}
+
+// Every note has a duration and the melody's length fits in a u16.
+fn is_valid_melody(melody: &[(u16, u16)]) -> bool {
+    melody.iter().all(|note| note.1 > 0)
+        && melody.iter().try_fold(0u16, |duration, note| duration.checked_add(note.1)).is_some()
+}
+
+fn to_ffi_chords(chromosome: &Chromosome) -> Vec<OrpheusChord> {
+    chromosome.get_genes()
+        .iter()
+        .map(|gene| OrpheusChord {
+            root_note: gene.get_root_note(),
+            chord_type: gene.get_chord_type(),
+            duration: gene.get_duration(),
+        })
+        .collect()
+}
+
+// Codes match ORPHEUS_STOP_* in include/orpheus_core.h.
+fn stop_reason_code(stop_reason: StopReason) -> u32 {
+    match stop_reason {
+        StopReason::GenerationLimit => 0,
+        StopReason::Stagnation => 1,
+        StopReason::TargetFitness => 2,
+        StopReason::TimeBudget => 3,
+        StopReason::LowDiversity => 4,
+        StopReason::ObserverStopped => 5,
+        StopReason::Cancelled => 6,
+    }
+}
+
+fn into_raw_parts<T>(items: Vec<T>) -> (*mut T, usize) {
+    let length = items.len();
+    (Box::into_raw(items.into_boxed_slice()) as *mut T, length)
+}
+
+unsafe fn from_raw_parts<T>(items: *mut T, length: usize) -> Box<[T]> {
+    unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(items, length)) }
+}
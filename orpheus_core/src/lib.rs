@@ -1,61 +1,39 @@
-mod composer;
+pub mod composer;
+pub mod ffi;
+pub mod melodies;
+pub mod theory;
 
-const INITIAL_POOL_SIZE: usize = 80;
-const SELECTION_POOL_SIZE: usize = 30;
-const OFFSPRING_TARGET: usize = 74;
-const ELITE_POOL_SIZE: usize = 6;
-const NUMBER_OF_GENERATIONS: usize = 1000;
-
-const MUTATION_RATE: f64 = 0.05;
+use crate::{composer::config::Config, theory::spelling::ChordSymbolStyle};
 
 pub fn test() {
 
-    // Melody note representation: (MIDI note code, duration in semiquavers)
-    // C - F - - - A F A - - - G - F - - - D - C - - - C - F - - - A F A - - - G - C - - - - - - - - -
-    // Melody: Amazing Grace
-    let test_melody: Vec::<(u16, u16)> = vec![
-        (60, 4), // C for 4 semiquavers
-        (65, 8), // F for 8 semiquavers
-        (69, 2), // A for 2 semiquavers
-        (65, 2), // F for 2 semiquavers
-        (69, 8), // A for 8 semiquavers
-        (67, 4), // G for 4 semiquavers
-        (65, 8), // F for 8 semiquavers
-        (62, 4), // D for 4 semiquavers
-        (60, 8), // C for 8 semiquavers, change to 4 with rest
-        //(0, 4), // Rest for 4 semiquavers
-        (60, 4), // C for 4 semiquavers
-        (65, 8), // F for 8 semiquavers
-        (69, 2), // A for 2 semiquavers
-        (65, 2), // F for 2 semiquavers
-        (69, 8), // A for 8 semiquavers
-        (67, 4), // G for 4 semiquavers
-        (72, 20) // C for 20 semiquavers
-    ];
-
-    let melody_duration = composer::calculate_melody_duration(&test_melody);
+    let config = Config::default();
 
-    let crossover_point = melody_duration / 2;
+    let test_melody = melodies::amazing_grace();
 
-    let mut pool = composer::generate_chromosome_pool(INITIAL_POOL_SIZE, melody_duration);
+    let melody_duration = composer::calculate_melody_duration(&test_melody);
 
-    for _ in 0..NUMBER_OF_GENERATIONS {
+    let composition = composer::compose(&test_melody, &config).expect("default config is valid");
 
-        composer::calculate_pool_fitness(&mut pool, &test_melody);
-        let selection_pool = composer::select_top_n_pool(&pool, SELECTION_POOL_SIZE);
+    for candidate in &composition.candidates {
+        candidate.print(&composition.key);
+        candidate.chromosome.validate(melody_duration);
+    }
 
-        let mut offspring = composer::crossover_selection(&selection_pool, OFFSPRING_TARGET, crossover_point);
-        composer::mutate_chromosome_pool(&mut offspring, MUTATION_RATE);
+    let (stop_reason, generations) = (composition.stop_reason, composition.generations);
+    println!("Stopped after {generations} generations: {stop_reason:?}");
 
-        let elite = composer::select_top_n_pool(&pool, ELITE_POOL_SIZE);
+    let style = ChordSymbolStyle::default();
+    if let Some(best) = composition.candidates.first() {
+        let (key_name, chord_symbols) = (composition.key.name(style.accidentals), best.chromosome.chord_symbols(&composition.key, &style).join(" "));
+        println!("Best progression in {key_name}: {chord_symbols}");
+    }
 
-        pool = offspring;
-        pool.extend(elite);
+    let key = config.key_for(&test_melody);
 
+    if let (Some(harmonization), Some(best)) = (composer::harmonizer::harmonize(&test_melody, &key, &config.fitness_weights), composition.candidates.first()) {
+        let (optimum, achieved) = (harmonization.score, best.chromosome.calculate_chord_melody_fitness(&test_melody, &key, &config.fitness_weights));
+        println!("Chord-melody fitness {achieved:.3} of an optimum {optimum:.3}");
     }
 
-    composer::calculate_pool_fitness(&mut pool, &test_melody);
-    let top_solutions = composer::select_top_n_pool(&pool, 5);
-    composer::print_chromosome_pool(&top_solutions, melody_duration);
-
 }
\ No newline at end of file
@@ -1,5 +1,3 @@
-use orpheus_core;
-
 fn main() {
 
     orpheus_core::test();
@@ -0,0 +1,28 @@
+// Example melodies for trying out and benchmarking the composer.
+
+// Melody note representation: (MIDI note code, duration in semiquavers)
+pub fn amazing_grace() -> Vec::<(u16, u16)> {
+
+    // C - F - - - A F A - - - G - F - - - D - C - - - C - F - - - A F A - - - G - C - - - - - - - - -
+    // Melody: Amazing Grace
+    vec![
+        (60, 4), // C for 4 semiquavers
+        (65, 8), // F for 8 semiquavers
+        (69, 2), // A for 2 semiquavers
+        (65, 2), // F for 2 semiquavers
+        (69, 8), // A for 8 semiquavers
+        (67, 4), // G for 4 semiquavers
+        (65, 8), // F for 8 semiquavers
+        (62, 4), // D for 4 semiquavers
+        (60, 8), // C for 8 semiquavers, change to 4 with rest
+        //(0, 4), // Rest for 4 semiquavers
+        (60, 4), // C for 4 semiquavers
+        (65, 8), // F for 8 semiquavers
+        (69, 2), // A for 2 semiquavers
+        (65, 2), // F for 2 semiquavers
+        (69, 8), // A for 8 semiquavers
+        (67, 4), // G for 4 semiquavers
+        (72, 20) // C for 20 semiquavers
+    ]
+
+}
@@ -0,0 +1,392 @@
+pub mod spelling;
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, ops::Add};
+
+// Music theory the composer builds on, in twelve-tone equal temperament with octaves folded away:
+// pitch classes, the intervals between them, scales, chords and keys.
+
+const PITCH_CLASS_NAMES: [&str; 12] = ["C", "C♯/D♭", "D", "D♯/E♭", "E", "F", "F♯/G♭", "G", "G♯/A♭", "A", "A♯/B♭", "B"];
+
+// Major and minor chords built on the key's scale, as (interval above the tonic, quality).
+// The minor key takes its dominant from the harmonic minor scale.
+const MAJOR_KEY_CHORDS: [(Interval, ChordQuality); 6] = [
+    (Interval::UNISON, ChordQuality::Major), (Interval::MAJOR_SECOND, ChordQuality::Minor), (Interval::MAJOR_THIRD, ChordQuality::Minor),
+    (Interval::PERFECT_FOURTH, ChordQuality::Major), (Interval::PERFECT_FIFTH, ChordQuality::Major), (Interval::MAJOR_SIXTH, ChordQuality::Minor),
+];
+const MINOR_KEY_CHORDS: [(Interval, ChordQuality); 6] = [
+    (Interval::UNISON, ChordQuality::Minor), (Interval::MINOR_THIRD, ChordQuality::Major), (Interval::PERFECT_FOURTH, ChordQuality::Minor),
+    (Interval::PERFECT_FIFTH, ChordQuality::Major), (Interval::MINOR_SIXTH, ChordQuality::Major), (Interval::MINOR_SEVENTH, ChordQuality::Major),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TheoryError {
+    InvalidPitchClass(u16),
+    InvalidChordQuality(u16),
+    InvalidChord(u16),
+}
+
+impl fmt::Display for TheoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TheoryError::InvalidPitchClass(pitch_class) => write!(f, "pitch class {pitch_class} is not in 0..12"),
+            TheoryError::InvalidChordQuality(quality) => write!(f, "chord quality {quality} is not major (0) or minor (1)"),
+            TheoryError::InvalidChord(chord) => write!(f, "chord index {chord} is not in 0..{}", Chord::COUNT),
+        }
+    }
+}
+
+impl std::error::Error for TheoryError {}
+
+// Pitch class, 0 (C) to 11 (B). Serialised as its number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "u16", into = "u16")]
+pub struct PitchClass(u8);
+
+impl PitchClass {
+
+    pub const fn new(pitch_class: u16) -> Result<PitchClass, TheoryError> {
+        if pitch_class >= 12 {
+            return Err(TheoryError::InvalidPitchClass(pitch_class));
+        }
+
+        Ok(PitchClass(pitch_class as u8))
+    }
+
+    // Pitch class the given number of semitones above C, wrapping round the octave, such as a MIDI note's.
+    pub const fn from_semitones(semitones: u16) -> PitchClass {
+        PitchClass((semitones % 12) as u8)
+    }
+
+    pub const fn get(&self) -> u16 {
+        self.0 as u16
+    }
+
+    pub const fn transpose(&self, interval: Interval) -> PitchClass {
+        PitchClass::from_semitones(self.0 as u16 + interval.semitones())
+    }
+
+    // Interval up from this pitch class to the other.
+    pub const fn interval_to(&self, other: PitchClass) -> Interval {
+        Interval::new(other.0 as u16 + 12 - self.0 as u16)
+    }
+
+    // Both spellings of a black key, such as "C♯/D♭".
+    pub fn name(&self) -> &'static str {
+        PITCH_CLASS_NAMES[self.0 as usize]
+    }
+}
+
+impl TryFrom<u16> for PitchClass {
+    type Error = TheoryError;
+
+    fn try_from(pitch_class: u16) -> Result<Self, Self::Error> {
+        PitchClass::new(pitch_class)
+    }
+}
+
+impl From<PitchClass> for u16 {
+    fn from(pitch_class: PitchClass) -> Self {
+        pitch_class.get()
+    }
+}
+
+impl Add<Interval> for PitchClass {
+    type Output = PitchClass;
+
+    fn add(self, interval: Interval) -> PitchClass {
+        self.transpose(interval)
+    }
+}
+
+// Interval class, 0 to 11 semitones, with compound intervals folded into the octave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval(u8);
+
+impl Interval {
+
+    pub const UNISON: Interval = Interval(0);
+    pub const MINOR_SECOND: Interval = Interval(1);
+    pub const MAJOR_SECOND: Interval = Interval(2);
+    pub const MINOR_THIRD: Interval = Interval(3);
+    pub const MAJOR_THIRD: Interval = Interval(4);
+    pub const PERFECT_FOURTH: Interval = Interval(5);
+    pub const TRITONE: Interval = Interval(6);
+    pub const PERFECT_FIFTH: Interval = Interval(7);
+    pub const MINOR_SIXTH: Interval = Interval(8);
+    pub const MAJOR_SIXTH: Interval = Interval(9);
+    pub const MINOR_SEVENTH: Interval = Interval(10);
+    pub const MAJOR_SEVENTH: Interval = Interval(11);
+
+    pub const fn new(semitones: u16) -> Interval {
+        Interval((semitones % 12) as u8)
+    }
+
+    pub const fn semitones(&self) -> u16 {
+        self.0 as u16
+    }
+
+    // Interval that makes up an octave with this one, such as a fourth for a fifth.
+    pub const fn invert(&self) -> Interval {
+        Interval::new(12 - self.0 as u16)
+    }
+
+    // Semitones to move by, up or down, whichever is fewer: 0 to 6.
+    pub const fn shortest_semitones(&self) -> u16 {
+        let inverted = self.invert().semitones();
+        if inverted < self.semitones() { inverted } else { self.semitones() }
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+
+    fn add(self, other: Interval) -> Interval {
+        Interval::new(self.semitones() + other.semitones())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Mode {
+    Ionian,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    Aeolian,
+    HarmonicMinor,
+    // Fifth mode of harmonic minor, for the major dominant of a minor key.
+    PhrygianDominant,
+}
+
+impl Mode {
+
+    // Intervals above the tonic of every note in the mode.
+    pub const fn intervals(&self) -> [Interval; 7] {
+        let semitones = match self {
+            Mode::Ionian => [0, 2, 4, 5, 7, 9, 11],
+            Mode::Dorian => [0, 2, 3, 5, 7, 9, 10],
+            Mode::Phrygian => [0, 1, 3, 5, 7, 8, 10],
+            Mode::Lydian => [0, 2, 4, 6, 7, 9, 11],
+            Mode::Mixolydian => [0, 2, 4, 5, 7, 9, 10],
+            Mode::Aeolian => [0, 2, 3, 5, 7, 8, 10],
+            Mode::HarmonicMinor => [0, 2, 3, 5, 7, 8, 11],
+            Mode::PhrygianDominant => [0, 1, 4, 5, 7, 8, 10],
+        };
+
+        let mut intervals = [Interval::UNISON; 7];
+        let mut degree = 0;
+
+        while degree < 7 {
+            intervals[degree] = Interval(semitones[degree]);
+            degree += 1;
+        }
+
+        intervals
+    }
+}
+
+// A mode built on a tonic, such as D Dorian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Scale {
+    pub tonic: PitchClass,
+    pub mode: Mode,
+}
+
+impl Scale {
+
+    pub const fn new(tonic: PitchClass, mode: Mode) -> Scale {
+        Scale { tonic, mode }
+    }
+
+    // Pitch classes of every note in the scale, tonic first.
+    pub const fn pitch_classes(&self) -> [PitchClass; 7] {
+        transpose_all(self.mode.intervals(), self.tonic)
+    }
+
+    pub fn contains(&self, pitch_class: PitchClass) -> bool {
+        self.pitch_classes().contains(&pitch_class)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChordQuality {
+    Major,
+    Minor,
+}
+
+impl ChordQuality {
+
+    pub const fn from_index(quality: u16) -> Result<ChordQuality, TheoryError> {
+        match quality {
+            0 => Ok(ChordQuality::Major),
+            1 => Ok(ChordQuality::Minor),
+            _ => Err(TheoryError::InvalidChordQuality(quality)),
+        }
+    }
+
+    pub const fn index(&self) -> u16 {
+        match self {
+            ChordQuality::Major => 0,
+            ChordQuality::Minor => 1,
+        }
+    }
+
+    // Intervals above the root of every note in the triad, root first.
+    pub const fn intervals(&self) -> [Interval; 3] {
+        match self {
+            ChordQuality::Major => [Interval::UNISON, Interval::MAJOR_THIRD, Interval::PERFECT_FIFTH],
+            ChordQuality::Minor => [Interval::UNISON, Interval::MINOR_THIRD, Interval::PERFECT_FIFTH],
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChordQuality::Major => "Major",
+            ChordQuality::Minor => "Minor",
+        }
+    }
+}
+
+// A triad, with an index of root * 2 + quality into the precomputed chord tables. Serialised as its index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u16", into = "u16")]
+pub struct Chord {
+    pub root: PitchClass,
+    pub quality: ChordQuality,
+}
+
+impl Chord {
+
+    pub const COUNT: usize = 24;
+
+    pub const fn new(root: PitchClass, quality: ChordQuality) -> Chord {
+        Chord { root, quality }
+    }
+
+    pub const fn from_index(index: u16) -> Result<Chord, TheoryError> {
+        if index as usize >= Chord::COUNT {
+            return Err(TheoryError::InvalidChord(index));
+        }
+
+        match ChordQuality::from_index(index & 1) {
+            Ok(quality) => Ok(Chord::new(PitchClass::from_semitones(index >> 1), quality)),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub const fn index(&self) -> u16 {
+        (self.root.get() << 1) + self.quality.index()
+    }
+
+    // Pitch classes of every note in the chord, root first.
+    pub const fn pitch_classes(&self) -> [PitchClass; 3] {
+        transpose_all(self.quality.intervals(), self.root)
+    }
+
+    pub fn contains(&self, pitch_class: PitchClass) -> bool {
+        self.pitch_classes().contains(&pitch_class)
+    }
+
+    pub const fn transpose(&self, interval: Interval) -> Chord {
+        Chord::new(self.root.transpose(interval), self.quality)
+    }
+}
+
+impl TryFrom<u16> for Chord {
+    type Error = TheoryError;
+
+    fn try_from(index: u16) -> Result<Self, Self::Error> {
+        Chord::from_index(index)
+    }
+}
+
+impl From<Chord> for u16 {
+    fn from(chord: Chord) -> Self {
+        chord.index()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Key {
+    pub tonic: PitchClass,
+    pub quality: ChordQuality,
+}
+
+impl Key {
+
+    pub const fn new(tonic: PitchClass, quality: ChordQuality) -> Key {
+        Key { tonic, quality }
+    }
+
+    // Every major and minor key, indexed like chords.
+    pub fn all() -> impl Iterator<Item = Key> {
+        (0..Chord::COUNT as u16).map(|index| {
+            let chord = Chord::from_index(index).expect("index is below the chord count");
+            Key::new(chord.root, chord.quality)
+        })
+    }
+
+    // Major scale for a major key and natural minor for a minor key.
+    pub const fn scale(&self) -> Scale {
+        match self.quality {
+            ChordQuality::Major => Scale::new(self.tonic, Mode::Ionian),
+            ChordQuality::Minor => Scale::new(self.tonic, Mode::Aeolian),
+        }
+    }
+
+    // Major and minor chords that belong to the key.
+    pub fn diatonic_chords(&self) -> Vec<Chord> {
+        let chords = match self.quality {
+            ChordQuality::Major => MAJOR_KEY_CHORDS,
+            ChordQuality::Minor => MINOR_KEY_CHORDS,
+        };
+
+        chords.iter().map(|&(interval, quality)| Chord::new(self.tonic + interval, quality)).collect()
+    }
+
+    // Scale implied by the chord's function in the key, such as Dorian for a ii chord in a major key.
+    // Chords from outside the key fall back to the major scale or harmonic minor.
+    pub const fn chord_mode(&self, chord: &Chord) -> Mode {
+
+        let degree = self.tonic.interval_to(chord.root).semitones();
+
+        match (self.quality, degree, chord.quality) {
+            (ChordQuality::Major, 0, ChordQuality::Major) => Mode::Ionian,
+            (ChordQuality::Major, 2, ChordQuality::Minor) => Mode::Dorian,
+            (ChordQuality::Major, 4, ChordQuality::Minor) => Mode::Phrygian,
+            (ChordQuality::Major, 5, ChordQuality::Major) => Mode::Lydian,
+            (ChordQuality::Major, 7, ChordQuality::Major) => Mode::Mixolydian,
+            (ChordQuality::Major, 9, ChordQuality::Minor) => Mode::Aeolian,
+            (ChordQuality::Minor, 0, ChordQuality::Minor) => Mode::Aeolian,
+            (ChordQuality::Minor, 3, ChordQuality::Major) => Mode::Ionian,
+            (ChordQuality::Minor, 5, ChordQuality::Minor) => Mode::Dorian,
+            (ChordQuality::Minor, 7, ChordQuality::Minor) => Mode::Phrygian,
+            (ChordQuality::Minor, 7, ChordQuality::Major) => Mode::PhrygianDominant,
+            (ChordQuality::Minor, 8, ChordQuality::Major) => Mode::Lydian,
+            (ChordQuality::Minor, 10, ChordQuality::Major) => Mode::Mixolydian,
+            (_, _, ChordQuality::Major) => Mode::Ionian,
+            (_, _, ChordQuality::Minor) => Mode::HarmonicMinor,
+        }
+
+    }
+
+    // Scale of the chord for its function in the key.
+    pub const fn chord_scale(&self, chord: &Chord) -> Scale {
+        Scale::new(chord.root, self.chord_mode(chord))
+    }
+}
+
+const fn transpose_all<const N: usize>(intervals: [Interval; N], root: PitchClass) -> [PitchClass; N] {
+
+    let mut pitch_classes = [root; N];
+    let mut index = 0;
+
+    while index < N {
+        pitch_classes[index] = root.transpose(intervals[index]);
+        index += 1;
+    }
+
+    pitch_classes
+
+}
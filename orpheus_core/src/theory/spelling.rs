@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+
+use crate::theory::{Chord, ChordQuality, Interval, Key, PitchClass};
+
+const LETTERS: [char; 7] = ['C', 'D', 'E', 'F', 'G', 'A', 'B'];
+const LETTER_PITCH_CLASSES: [u16; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+// Letter index of every key's tonic by pitch class, choosing the key signature with fewer accidentals
+// and sharps where both have six: D♭ major but C♯ minor, F♯ major and D♯ minor.
+const MAJOR_TONIC_LETTERS: [usize; 12] = [0, 1, 1, 2, 2, 3, 3, 4, 5, 5, 6, 6];
+const MINOR_TONIC_LETTERS: [usize; 12] = [0, 0, 1, 1, 2, 3, 3, 4, 4, 5, 6, 6];
+
+// Letter index of chromatic notes in a key without sharps or flats, spelt as they most often appear
+// in C major and A minor: C♯, E♭, F♯, A♭ and B♭.
+const NATURAL_KEY_LETTERS: [usize; 12] = [0, 0, 1, 2, 2, 3, 3, 4, 5, 5, 6, 6];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Accidentals {
+    // ♯, ♭, 𝄪 and 𝄫.
+    #[default]
+    Unicode,
+    // #, b, ## and bb.
+    Ascii,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MinorSuffix {
+    // Cm
+    #[default]
+    Lowercase,
+    // C-
+    Dash,
+    // Cmin
+    Min,
+}
+
+// How chord symbols are written on a lead sheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChordSymbolStyle {
+    pub accidentals: Accidentals,
+    pub minor_suffix: MinorSuffix,
+}
+
+// A pitch class written as a letter and an accidental, from -2 for a double flat to 2 for a double sharp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Spelling {
+    pub letter: char,
+    pub accidental: i8,
+}
+
+impl Spelling {
+
+    // The letter at `letter_index`, wrapping round past B, with whatever accidental makes it the pitch class.
+    fn from_letter(letter_index: usize, pitch_class: PitchClass) -> Spelling {
+        let letter_index = letter_index % LETTERS.len();
+        let accidental = (pitch_class.get() + 18 - LETTER_PITCH_CLASSES[letter_index]) % 12;
+
+        Spelling { letter: LETTERS[letter_index], accidental: accidental as i8 - 6 }
+    }
+
+    fn letter_index(&self) -> usize {
+        LETTERS.iter().position(|&letter| letter == self.letter).expect("spellings only use the seven letters")
+    }
+
+    pub fn pitch_class(&self) -> PitchClass {
+        PitchClass::from_semitones((LETTER_PITCH_CLASSES[self.letter_index()] as i16 + 12 + self.accidental as i16) as u16)
+    }
+
+    pub fn render(&self, accidentals: Accidentals) -> String {
+        let accidental = match (accidentals, self.accidental) {
+            (Accidentals::Unicode, 2) => "𝄪".to_string(),
+            (Accidentals::Unicode, -2) => "𝄫".to_string(),
+            (Accidentals::Unicode, sharps @ 1..) => "♯".repeat(sharps as usize),
+            (Accidentals::Unicode, flats) => "♭".repeat(flats.unsigned_abs() as usize),
+            (Accidentals::Ascii, sharps @ 1..) => "#".repeat(sharps as usize),
+            (Accidentals::Ascii, flats) => "b".repeat(flats.unsigned_abs() as usize),
+        };
+
+        format!("{}{accidental}", self.letter)
+    }
+}
+
+impl Key {
+
+    fn tonic_spelling(&self) -> Spelling {
+        let tonic_letters = match self.quality {
+            ChordQuality::Major => MAJOR_TONIC_LETTERS,
+            ChordQuality::Minor => MINOR_TONIC_LETTERS,
+        };
+
+        Spelling::from_letter(tonic_letters[self.tonic.get() as usize], self.tonic)
+    }
+
+    // Every note of the key's scale on consecutive letters from the tonic,
+    // with a minor key's raised seventh from harmonic minor on the same letter as its seventh.
+    fn diatonic_spellings(&self) -> Vec<Spelling> {
+
+        let tonic_letter = self.tonic_spelling().letter_index();
+
+        let mut spellings: Vec<Spelling> = self.scale().pitch_classes()
+            .iter()
+            .enumerate()
+            .map(|(degree, &pitch_class)| Spelling::from_letter(tonic_letter + degree, pitch_class))
+            .collect();
+
+        if self.quality == ChordQuality::Minor {
+            spellings.push(Spelling::from_letter(tonic_letter + 6, self.tonic.transpose(Interval::MAJOR_SEVENTH)));
+        }
+
+        spellings
+
+    }
+
+    // The pitch class spelt for this key, such as D♭ in A♭ major and C♯ in A major.
+    // Notes outside the key are sharpened in sharp keys and flattened in flat keys, as are double sharps
+    // such as the raised seventh of G♯ minor, so a chord built on the note never needs more than a double accidental.
+    pub fn spell(&self, pitch_class: PitchClass) -> Spelling {
+
+        let diatonic_spellings = self.diatonic_spellings();
+
+        if let Some(&spelling) = diatonic_spellings.iter().find(|spelling| spelling.pitch_class() == pitch_class && spelling.accidental.abs() < 2) {
+            return spelling;
+        }
+
+        let natural_letter = LETTER_PITCH_CLASSES.iter().position(|&natural| natural == pitch_class.get());
+        let key_signature = diatonic_spellings.iter().take(7).map(|spelling| spelling.accidental).sum::<i8>();
+
+        match (natural_letter, key_signature.signum()) {
+            (Some(letter_index), _) => Spelling::from_letter(letter_index, pitch_class),
+            (None, 1) => Spelling::from_letter(LETTER_PITCH_CLASSES.iter().rposition(|&natural| natural < pitch_class.get()).expect("C is below every black key"), pitch_class),
+            (None, -1) => Spelling::from_letter(LETTER_PITCH_CLASSES.iter().position(|&natural| natural > pitch_class.get()).expect("B is above every black key"), pitch_class),
+            (None, _) => Spelling::from_letter(NATURAL_KEY_LETTERS[pitch_class.get() as usize], pitch_class),
+        }
+
+    }
+
+    // Such as "A♭ major" or "F♯ minor".
+    pub fn name(&self, accidentals: Accidentals) -> String {
+        let quality = match self.quality {
+            ChordQuality::Major => "major",
+            ChordQuality::Minor => "minor",
+        };
+
+        format!("{} {quality}", self.tonic_spelling().render(accidentals))
+    }
+}
+
+impl Chord {
+
+    // Root, third and fifth on alternate letters from the root as spelt in the key,
+    // so E major in C major has a G♯ rather than an A♭.
+    pub fn spell(&self, key: &Key) -> [Spelling; 3] {
+        let root = key.spell(self.root);
+        let root_letter = root.letter_index();
+        let [_, third, fifth] = self.pitch_classes();
+
+        [root, Spelling::from_letter(root_letter + 2, third), Spelling::from_letter(root_letter + 4, fifth)]
+    }
+
+    // Chord symbol spelt for the key, such as "D♭" in A♭ major or "C♯m" in A major.
+    pub fn symbol(&self, key: &Key, style: &ChordSymbolStyle) -> String {
+        let suffix = match (self.quality, style.minor_suffix) {
+            (ChordQuality::Major, _) => "",
+            (ChordQuality::Minor, MinorSuffix::Lowercase) => "m",
+            (ChordQuality::Minor, MinorSuffix::Dash) => "-",
+            (ChordQuality::Minor, MinorSuffix::Min) => "min",
+        };
+
+        format!("{}{suffix}", key.spell(self.root).render(style.accidentals))
+    }
+
+    // Chord symbol over a bass note, such as "C/E". A chord tone in the bass is spelt as it is in the chord.
+    pub fn slash_symbol(&self, bass: PitchClass, key: &Key, style: &ChordSymbolStyle) -> String {
+
+        let symbol = self.symbol(key, style);
+
+        if bass == self.root {
+            return symbol;
+        }
+
+        let bass_spelling = self.spell(key)
+            .into_iter()
+            .find(|spelling| spelling.pitch_class() == bass)
+            .unwrap_or_else(|| key.spell(bass));
+
+        format!("{symbol}/{}", bass_spelling.render(style.accidentals))
+
+    }
+}
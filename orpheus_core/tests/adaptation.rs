@@ -0,0 +1,96 @@
+use orpheus_core::{
+    composer::{
+        adaptation::MutationSchedule,
+        config::{Config, MutationConfig},
+        genetic_composer::GeneticComposer,
+        progress::{CancellationToken, Control},
+        stopping::StoppingCriteria,
+    },
+    melodies,
+};
+
+#[test]
+fn constant_schedule_keeps_the_configured_rates() {
+    for generation in [0, 1, 500] {
+        assert_eq!(MutationSchedule::Constant.scale(generation, 0.0), 1.0);
+    }
+}
+
+#[test]
+fn exponential_decay_shrinks_every_generation_down_to_the_minimum() {
+    let schedule = MutationSchedule::ExponentialDecay { decay: 0.5, minimum_scale: 0.1 };
+
+    assert_eq!(schedule.scale(0, 1.0), 1.0);
+    assert_eq!(schedule.scale(1, 1.0), 0.5);
+    assert_eq!(schedule.scale(3, 1.0), 0.125);
+    assert_eq!(schedule.scale(4, 1.0), 0.1);
+    assert_eq!(schedule.scale(usize::MAX, 1.0), 0.1);
+}
+
+#[test]
+fn diversity_trigger_boosts_rates_as_diversity_falls_below_the_threshold() {
+    let schedule = MutationSchedule::DiversityTriggered { diversity_threshold: 0.4, boost: 3.0 };
+
+    assert_eq!(schedule.scale(10, 0.8), 1.0);
+    assert_eq!(schedule.scale(10, 0.4), 1.0);
+    assert_eq!(schedule.scale(10, 0.2), 2.0);
+    assert_eq!(schedule.scale(10, 0.0), 3.0);
+
+    let disabled = MutationSchedule::DiversityTriggered { diversity_threshold: 0.0, boost: 3.0 };
+    assert_eq!(disabled.scale(10, 0.0), 1.0);
+}
+
+#[test]
+fn scaled_rates_are_capped_at_certainty() {
+    let scaled = MutationConfig::uniform(0.4).scaled(3.0);
+
+    assert_eq!(scaled, MutationConfig::uniform(1.0));
+    assert_eq!(MutationConfig::uniform(0.4).scaled(0.5), MutationConfig::uniform(0.2));
+}
+
+#[test]
+fn diversity_is_only_measured_when_needed() {
+    let melody = melodies::amazing_grace();
+    let config = Config { seed: Some(3), ..Config::default() };
+    let criteria = StoppingCriteria { max_generations: 2, ..StoppingCriteria::default() };
+    let minimum_diversity = StoppingCriteria { minimum_diversity: Some(0.01), ..criteria.clone() };
+    let triggered = Config { mutation_schedule: MutationSchedule::DiversityTriggered { diversity_threshold: 0.3, boost: 2.0 }, ..config.clone() };
+
+    for (config, criteria, measured) in [(&config, &criteria, false), (&config, &minimum_diversity, true), (&triggered, &criteria, true)] {
+        let mut genetic_composer = GeneticComposer::new(&melody, config).unwrap();
+        let mut diversities = Vec::new();
+
+        genetic_composer.run_until(criteria, |stats| {
+            diversities.push(stats.diversity);
+            Control::Continue
+        }, &CancellationToken::new());
+
+        assert!(diversities.iter().all(|diversity| diversity.is_some() == measured));
+        assert!(genetic_composer.stats().diversity.is_some());
+    }
+}
+
+#[test]
+fn running_reuses_the_measured_diversity_without_changing_the_run() {
+    let melody = melodies::amazing_grace();
+    let config = Config {
+        mutation_schedule: MutationSchedule::DiversityTriggered { diversity_threshold: 0.3, boost: 2.0 },
+        seed: Some(4),
+        ..Config::default()
+    };
+    let mut run = GeneticComposer::new(&melody, &config).unwrap();
+    let mut stepped = run.clone();
+    let mut scales = Vec::new();
+
+    run.run_until(&StoppingCriteria { max_generations: 5, ..StoppingCriteria::default() }, |stats| {
+        scales.push(stats.mutation_scale);
+        Control::Continue
+    }, &CancellationToken::new());
+
+    for scale in &scales[..5] {
+        assert_eq!(stepped.current_mutation_scale(), *scale);
+        stepped.step();
+    }
+
+    assert_eq!(run.population(), stepped.population());
+}
@@ -0,0 +1,118 @@
+use orpheus_core::{
+    composer::{
+        chromosome::gene::{self, GeneError},
+        config::Config,
+        genetic_composer::{GeneticComposer, checkpoint::{self, CheckpointError}},
+    },
+    melodies,
+};
+use serde_json::Value;
+
+fn genetic_composer() -> GeneticComposer {
+    let mut genetic_composer = GeneticComposer::new(&melodies::amazing_grace(), &Config { seed: Some(11), ..Config::default() }).unwrap();
+
+    for _ in 0..3 {
+        genetic_composer.step();
+    }
+
+    genetic_composer
+}
+
+fn checkpoint_json(genetic_composer: &GeneticComposer) -> Value {
+    let mut checkpoint = Vec::new();
+    genetic_composer.write_checkpoint(&mut checkpoint).unwrap();
+    serde_json::from_slice(&checkpoint).unwrap()
+}
+
+fn read(checkpoint: &Value) -> Result<GeneticComposer, CheckpointError> {
+    GeneticComposer::read_checkpoint(serde_json::to_vec(checkpoint).unwrap().as_slice())
+}
+
+#[test]
+fn resumed_runs_continue_exactly_where_they_left_off() {
+    let mut genetic_composer = genetic_composer();
+    let mut resumed = read(&checkpoint_json(&genetic_composer)).unwrap();
+
+    assert_eq!(resumed.population(), genetic_composer.population());
+    assert_eq!(resumed.generation(), genetic_composer.generation());
+
+    for _ in 0..5 {
+        genetic_composer.step();
+        resumed.step();
+    }
+
+    assert_eq!(resumed.population(), genetic_composer.population());
+
+    let fitnesses = |genetic_composer: &GeneticComposer| genetic_composer.population().iter().map(|chromosome| chromosome.get_fitness()).collect::<Vec<f64>>();
+    assert_eq!(fitnesses(&resumed), fitnesses(&genetic_composer));
+}
+
+#[test]
+fn saved_fitness_is_recalculated() {
+    let genetic_composer = genetic_composer();
+    let mut checkpoint = checkpoint_json(&genetic_composer);
+
+    // Claim the least fit chromosome is the fittest.
+    let population = checkpoint["population"].as_array_mut().unwrap();
+    population.last_mut().unwrap()["fitness"] = Value::from(100.0);
+    population.rotate_right(1);
+
+    let resumed = read(&checkpoint).unwrap();
+
+    assert_eq!(resumed.best(), genetic_composer.best());
+    assert_eq!(resumed.best().get_fitness(), genetic_composer.best().get_fitness());
+}
+
+#[test]
+fn version_one_checkpoints_load_without_ratings() {
+    let genetic_composer = genetic_composer();
+    let mut checkpoint = checkpoint_json(&genetic_composer);
+
+    checkpoint["format_version"] = Value::from(1);
+    checkpoint.as_object_mut().unwrap().remove("ratings");
+    for record in checkpoint["population"].as_array_mut().unwrap() {
+        record["fitness"] = Value::from(0);
+    }
+
+    let resumed = read(&checkpoint).unwrap();
+
+    assert!(resumed.ratings().is_empty());
+    assert_eq!(resumed.population(), genetic_composer.population());
+    assert_eq!(resumed.best().get_fitness(), genetic_composer.best().get_fitness());
+}
+
+#[test]
+fn other_versions_are_rejected() {
+    let mut checkpoint = checkpoint_json(&genetic_composer());
+    checkpoint["format_version"] = Value::from(checkpoint::FORMAT_VERSION + 1);
+
+    assert!(matches!(read(&checkpoint), Err(CheckpointError::UnsupportedFormatVersion(version)) if version == checkpoint::FORMAT_VERSION + 1));
+
+    checkpoint["format_version"] = Value::from(checkpoint::OLDEST_FORMAT_VERSION - 1);
+
+    assert!(matches!(read(&checkpoint), Err(CheckpointError::UnsupportedFormatVersion(0))));
+
+    let mut checkpoint = checkpoint_json(&genetic_composer());
+    checkpoint["gene_encoding_version"] = Value::from(gene::ENCODING_VERSION + 1);
+
+    assert!(matches!(read(&checkpoint), Err(CheckpointError::IncompatibleGeneEncoding(version)) if version == gene::ENCODING_VERSION + 1));
+}
+
+#[test]
+fn invalid_genes_are_rejected() {
+    let mut checkpoint = checkpoint_json(&genetic_composer());
+
+    // Root note 12 is not a pitch class.
+    checkpoint["population"][2]["genes"][0] = Value::from(12 << 6);
+
+    assert!(matches!(read(&checkpoint), Err(CheckpointError::InvalidGene(GeneError::InvalidRootNote(12)))));
+}
+
+#[test]
+fn chromosomes_not_lasting_as_long_as_the_melody_are_rejected() {
+    let mut checkpoint = checkpoint_json(&genetic_composer());
+
+    checkpoint["population"][2]["genes"].as_array_mut().unwrap().pop();
+
+    assert!(matches!(read(&checkpoint), Err(CheckpointError::InvalidChromosome(2))));
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::{key, pitch_class};
+use orpheus_core::{
+    composer::chromosome::{
+        self,
+        fitness::NoteFit,
+        precomputed_chord_notes::{CHORD_COUNT, chord_notes, chord_scale_notes},
+    },
+    theory::{Chord, ChordQuality, Mode, PitchClass},
+};
+
+fn chord(root_note: u16, quality: ChordQuality) -> Chord {
+    Chord::new(pitch_class(root_note), quality)
+}
+
+fn pitch_classes(notes: &[PitchClass]) -> Vec<u16> {
+    notes.iter().map(PitchClass::get).collect()
+}
+
+fn sorted(notes: &[PitchClass]) -> Vec<u16> {
+    let mut notes = pitch_classes(notes);
+    notes.sort();
+    notes
+}
+
+fn chords() -> impl Iterator<Item = Chord> {
+    (0..CHORD_COUNT as u16).map(|index| Chord::from_index(index).unwrap())
+}
+
+#[test]
+fn chords_are_spelled_from_their_root() {
+    // C major: C E G
+    assert_eq!(pitch_classes(chord_notes(chord(0, ChordQuality::Major))), [0, 4, 7]);
+    // A minor: A C E
+    assert_eq!(pitch_classes(chord_notes(chord(9, ChordQuality::Minor))), [9, 0, 4]);
+    // F♯ major: F♯ A♯ C♯
+    assert_eq!(pitch_classes(chord_notes(chord(6, ChordQuality::Major))), [6, 10, 1]);
+    // B♭ minor: B♭ D♭ F
+    assert_eq!(pitch_classes(chord_notes(chord(10, ChordQuality::Minor))), [10, 1, 5]);
+    // B major: B D♯ F♯
+    assert_eq!(pitch_classes(chord_notes(chord(11, ChordQuality::Major))), [11, 3, 6]);
+}
+
+#[test]
+fn scales_follow_the_chords_function_in_the_key() {
+    let c_major = key(0, ChordQuality::Major);
+    let a_minor = key(9, ChordQuality::Minor);
+
+    // I, ii, iii, IV, V and vi of C major.
+    assert_eq!(c_major.chord_mode(&chord(0, ChordQuality::Major)), Mode::Ionian);
+    assert_eq!(c_major.chord_mode(&chord(2, ChordQuality::Minor)), Mode::Dorian);
+    assert_eq!(c_major.chord_mode(&chord(4, ChordQuality::Minor)), Mode::Phrygian);
+    assert_eq!(c_major.chord_mode(&chord(5, ChordQuality::Major)), Mode::Lydian);
+    assert_eq!(c_major.chord_mode(&chord(7, ChordQuality::Major)), Mode::Mixolydian);
+    assert_eq!(c_major.chord_mode(&chord(9, ChordQuality::Minor)), Mode::Aeolian);
+
+    // i and the harmonic minor dominant of A minor.
+    assert_eq!(a_minor.chord_mode(&chord(9, ChordQuality::Minor)), Mode::Aeolian);
+    assert_eq!(a_minor.chord_mode(&chord(4, ChordQuality::Major)), Mode::PhrygianDominant);
+
+    // Chords from outside the key.
+    assert_eq!(c_major.chord_mode(&chord(1, ChordQuality::Major)), Mode::Ionian);
+    assert_eq!(c_major.chord_mode(&chord(1, ChordQuality::Minor)), Mode::HarmonicMinor);
+}
+
+#[test]
+fn scales_are_spelled_from_their_root() {
+    let c_major = key(0, ChordQuality::Major);
+
+    // D Dorian: D E F G A B C
+    assert_eq!(pitch_classes(chord_scale_notes(chord(2, ChordQuality::Minor), &c_major)), [2, 4, 5, 7, 9, 11, 0]);
+    // G Mixolydian: G A B C D E F
+    assert_eq!(sorted(chord_scale_notes(chord(7, ChordQuality::Major), &c_major)), [0, 2, 4, 5, 7, 9, 11]);
+    // E Phrygian dominant: E F G♯ A B C D
+    assert_eq!(sorted(chord_scale_notes(chord(4, ChordQuality::Major), &key(9, ChordQuality::Minor))), [0, 2, 4, 5, 8, 9, 11]);
+    // E♭ major outside C major: E♭ F G A♭ B♭ C D
+    assert_eq!(sorted(chord_scale_notes(chord(3, ChordQuality::Major), &c_major)), [0, 2, 3, 5, 7, 8, 10]);
+}
+
+#[test]
+fn every_chord_is_in_its_scale() {
+    for key in [key(0, ChordQuality::Major), key(9, ChordQuality::Minor), key(6, ChordQuality::Minor)] {
+        for chord in chords() {
+            assert_eq!(chord_notes(chord)[0], chord.root);
+            for note in chord_notes(chord) {
+                assert!(chord_scale_notes(chord, &key).contains(note), "{chord:?} note {note:?} is outside its scale in {key:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn root_is_a_chord_tone() {
+    for chord in chords() {
+        assert_eq!(chromosome::classify_melody_note(chord, chord.root, &key(0, ChordQuality::Major)), NoteFit::ChordTone);
+    }
+}
+
+#[test]
+fn scale_tones_depend_on_the_key() {
+    // B is the raised sixth of D Dorian, the ii of C major, but clashes with D Aeolian, the vi of F major.
+    let d_minor = chord(2, ChordQuality::Minor);
+    assert_eq!(chromosome::classify_melody_note(d_minor, pitch_class(11), &key(0, ChordQuality::Major)), NoteFit::ScaleTone);
+    assert_eq!(chromosome::classify_melody_note(d_minor, pitch_class(11), &key(5, ChordQuality::Major)), NoteFit::Clash);
+}
@@ -0,0 +1,41 @@
+// Fixtures shared by the integration tests. Each test crate only uses some of them.
+#![allow(dead_code)]
+
+use orpheus_core::{
+    composer::chromosome::{self, Chromosome, gene::{self, Gene}},
+    theory::{ChordQuality, Key, PitchClass},
+};
+use proptest::prelude::*;
+
+pub fn gene_strategy() -> impl Strategy<Value = Gene> {
+    (0u16..=gene::MAX_ROOT_NOTE, 0u16..=gene::MAX_CHORD_TYPE, 1u16..=gene::MAX_DURATION)
+        .prop_map(|(root_note, chord_type, duration)| gene::generate_gene(root_note, chord_type, duration).unwrap())
+}
+
+pub fn chromosome_strategy() -> impl Strategy<Value = Chromosome> {
+    prop::collection::vec(gene_strategy(), 1..16).prop_map(chromosome::generate_chromosome)
+}
+
+// Melody note representation: (MIDI note code, duration in semiquavers)
+pub fn melody_strategy() -> impl Strategy<Value = Vec<(u16, u16)>> {
+    prop::collection::vec((48u16..84, 1u16..=16), 1..24)
+}
+
+// (root note, chord type, duration) of every chord.
+pub fn chromosome(chords: &[(u16, u16, u16)], fitness: f64) -> Chromosome {
+    let genes = chords.iter()
+        .map(|&(root_note, chord_type, duration)| gene::generate_gene(root_note, chord_type, duration).unwrap())
+        .collect();
+
+    let mut chromosome = chromosome::generate_chromosome(genes);
+    chromosome.set_fitness(fitness);
+    chromosome
+}
+
+pub fn pitch_class(pitch_class: u16) -> PitchClass {
+    PitchClass::new(pitch_class).unwrap()
+}
+
+pub fn key(tonic: u16, quality: ChordQuality) -> Key {
+    Key::new(pitch_class(tonic), quality)
+}
@@ -0,0 +1,87 @@
+use orpheus_core::{
+    composer::{
+        self,
+        config::{Config, ConfigError},
+        genetic_composer::GeneticComposer,
+        islands::{IslandModel, MigrationConfig},
+        seeding::{ProgressionError, SeedingConfig},
+    },
+    melodies,
+};
+
+#[test]
+fn default_config_is_valid() {
+    assert_eq!(Config::default().validate(), Ok(()));
+}
+
+#[test]
+fn configs_that_empty_the_population_are_rejected() {
+    let melody = melodies::amazing_grace();
+
+    let no_initial_pool = Config { initial_pool_size: 0, ..Config::default() };
+    let no_next_generation = Config { offspring_target: 0, elite_pool_size: 0, ..Config::default() };
+    let no_parents = Config { selection_pool_size: 0, ..Config::default() };
+
+    assert_eq!(GeneticComposer::new(&melody, &no_initial_pool).err(), Some(ConfigError::EmptyInitialPool));
+    assert_eq!(GeneticComposer::new(&melody, &no_next_generation).err(), Some(ConfigError::EmptyNextGeneration));
+    assert_eq!(GeneticComposer::new(&melody, &no_parents).err(), Some(ConfigError::EmptySelectionPool));
+    assert_eq!(composer::compose(&melody, &no_initial_pool).err(), Some(ConfigError::EmptyInitialPool));
+    assert_eq!(IslandModel::new(&melody, &[Config::default(), no_next_generation], MigrationConfig::default()).err(), Some(ConfigError::EmptyNextGeneration));
+}
+
+#[test]
+fn island_models_need_a_config() {
+    assert_eq!(IslandModel::new(&melodies::amazing_grace(), &[], MigrationConfig::default()).err(), Some(ConfigError::NoIslands));
+}
+
+#[test]
+fn sharing_radii_must_be_positive_and_finite() {
+    for sharing_radius in [0.0, -0.25, f64::NAN, f64::INFINITY] {
+        let config = Config { fitness_sharing_radius: Some(sharing_radius), ..Config::default() };
+        assert_eq!(config.validate(), Err(ConfigError::InvalidSharingRadius));
+    }
+
+    assert_eq!(Config { fitness_sharing_radius: None, ..Config::default() }.validate(), Ok(()));
+}
+
+#[test]
+fn seeding_progressions_must_parse() {
+    let seeding = |progression: &str| SeedingConfig { progressions: vec!["C G Am F".to_string(), progression.to_string()], ..SeedingConfig::default() };
+    let melody = melodies::amazing_grace();
+
+    assert_eq!(Config { seeding: seeding("Dm:8 G:8 C"), ..Config::default() }.validate(), Ok(()));
+    assert_eq!(Config { seeding: seeding("C H"), ..Config::default() }.validate(), Err(ConfigError::InvalidProgression(ProgressionError::InvalidChord("H".to_string()))));
+    assert_eq!(
+        GeneticComposer::new(&melody, &Config { seeding: seeding("C:0"), ..Config::default() }).err(),
+        Some(ConfigError::InvalidProgression(ProgressionError::InvalidDuration("C:0".to_string()))),
+    );
+    assert_eq!(composer::compose(&melody, &Config { seeding: seeding("|"), ..Config::default() }).err(), Some(ConfigError::InvalidProgression(ProgressionError::Empty)));
+}
+
+#[test]
+fn melodies_without_duration_are_rejected() {
+    let config = Config { seed: Some(1), ..Config::default() };
+
+    for melody in [vec![], vec![(60, 0)]] {
+        assert_eq!(GeneticComposer::new(&melody, &config).err(), Some(ConfigError::EmptyMelody));
+        assert_eq!(composer::compose(&melody, &config).err(), Some(ConfigError::EmptyMelody));
+    }
+}
+
+#[test]
+fn elites_alone_or_a_single_parent_keep_the_run_going() {
+    let melody = melodies::amazing_grace();
+
+    for config in [
+        Config { offspring_target: 0, selection_pool_size: 0, seed: Some(1), ..Config::default() },
+        Config { selection_pool_size: 1, seed: Some(1), ..Config::default() },
+    ] {
+        let mut genetic_composer = GeneticComposer::new(&melody, &config).unwrap();
+
+        for _ in 0..5 {
+            genetic_composer.step();
+        }
+
+        assert!(!genetic_composer.population().is_empty());
+    }
+}
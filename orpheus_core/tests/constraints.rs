@@ -0,0 +1,98 @@
+use orpheus_core::{
+    composer::{
+        self,
+        chromosome::{self, Chromosome, gene},
+        config::MutationConfig,
+        constraints::{self, Constraint},
+        crossover::{self, Crossover},
+        results::ChordDistance,
+    },
+    theory::{Chord, ChordQuality, Key, PitchClass},
+};
+use proptest::prelude::*;
+use rand::{SeedableRng, rngs::StdRng};
+
+const MELODY_DURATION: u16 = 128;
+
+fn constraints_strategy() -> impl Strategy<Value = Vec<Constraint>> {
+    (0u16..MELODY_DURATION, 1u16..48, 0u16..Chord::COUNT as u16, 0u16..MELODY_DURATION, 0u16..=gene::MAX_ROOT_NOTE)
+        .prop_map(|(lock_start, lock_length, chord, diatonic_start, tonic)| vec![
+            Constraint::DiatonicOnly { start: diatonic_start, end: MELODY_DURATION, key: Key::new(PitchClass::new(tonic).unwrap(), ChordQuality::Major) },
+            Constraint::LockChord { start: lock_start, end: lock_start + lock_length, chord: Chord::from_index(chord).unwrap() },
+        ])
+}
+
+fn assert_constraints_met(chromosome: &Chromosome, constraints: &[Constraint]) -> Result<(), TestCaseError> {
+    let timeline = ChordDistance::Timeline.profile(chromosome);
+    prop_assert_eq!(timeline.len(), MELODY_DURATION as usize);
+
+    let Constraint::LockChord { start, end, chord: locked_chord } = constraints[1] else { unreachable!() };
+    let end = end.min(MELODY_DURATION);
+    prop_assert!(timeline[start as usize..end as usize].iter().all(|&chord| chord == locked_chord));
+
+    let Constraint::DiatonicOnly { start: diatonic_start, key, .. } = constraints[0] else { unreachable!() };
+    let diatonic_chords = key.diatonic_chords();
+    for (time, chord) in timeline.iter().enumerate().skip(diatonic_start as usize) {
+        prop_assert!((start as usize..end as usize).contains(&time) || diatonic_chords.contains(chord));
+    }
+
+    Ok(())
+}
+
+proptest! {
+
+    #[test]
+    fn enforced_chromosomes_meet_constraints(constraints in constraints_strategy(), seed in any::<u64>()) {
+        let mut chromosome = chromosome::generate_random_chromosome(&mut StdRng::seed_from_u64(seed), MELODY_DURATION);
+        constraints::enforce_constraints(&constraints, &mut chromosome);
+
+        assert_constraints_met(&chromosome, &constraints)?;
+
+        let mut enforced_again = chromosome.clone();
+        constraints::enforce_constraints(&constraints, &mut enforced_again);
+        prop_assert_eq!(enforced_again, chromosome);
+    }
+
+    #[test]
+    fn locked_regions_survive_crossover_and_mutation(constraints in constraints_strategy(), seed in any::<u64>()) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut pool = composer::generate_chromosome_pool(&mut rng, 2, MELODY_DURATION);
+        constraints::enforce_pool_constraints(&constraints, &mut pool);
+
+        let (mut child_one, mut child_two) = crossover::crossover_parents(&pool[0], &pool[1], Crossover::TwoPoint, &mut rng);
+        for child in [&mut child_one, &mut child_two] {
+            child.mutate(&MutationConfig::uniform(0.5), &mut rng).unwrap();
+            constraints::enforce_constraints(&constraints, child);
+            assert_constraints_met(child, &constraints)?;
+        }
+    }
+
+    #[test]
+    fn locked_genes_are_kept(seed in any::<u64>(), locked_gene_count in 1usize..4) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let liked = chromosome::generate_random_chromosome(&mut rng, MELODY_DURATION);
+        let locked_gene_count = locked_gene_count.min(liked.get_genes().len());
+        let constraints = vec![Constraint::lock_genes(&liked, 0..locked_gene_count)];
+
+        let mut rerolled = chromosome::generate_random_chromosome(&mut rng, MELODY_DURATION);
+        constraints::enforce_constraints(&constraints, &mut rerolled);
+
+        prop_assert_eq!(rerolled.get_duration(), MELODY_DURATION);
+        prop_assert_eq!(&rerolled.get_genes()[..locked_gene_count], &liked.get_genes()[..locked_gene_count]);
+    }
+}
+
+#[test]
+fn constraints_serialise_chords_by_index_and_genes_by_encoding() {
+    let gene = gene::generate_gene(9, 1, 8).unwrap();
+    let constraints = vec![
+        Constraint::LockChord { start: 0, end: 16, chord: gene.chord() },
+        Constraint::LockGenes { start: 16, genes: vec![gene] },
+    ];
+
+    let json = serde_json::to_string(&constraints).unwrap();
+
+    assert_eq!(json, format!(r#"[{{"LockChord":{{"start":0,"end":16,"chord":19}}}},{{"LockGenes":{{"start":16,"genes":[{}]}}}}]"#, gene.encode()));
+    assert_eq!(serde_json::from_str::<Vec<Constraint>>(&json).unwrap(), constraints);
+    assert!(serde_json::from_str::<Vec<Constraint>>(r#"[{"LockChord":{"start":0,"end":16,"chord":24}}]"#).is_err());
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{chromosome_strategy, melody_strategy};
+use orpheus_core::composer::{self, chromosome::{self, gene::Gene}, config::MutationConfig, crossover::{self, Crossover}, results::ChordDistance};
+use proptest::prelude::*;
+use rand::{SeedableRng, rngs::StdRng};
+
+fn crossover_strategy() -> impl Strategy<Value = Crossover> {
+    prop_oneof![Just(Crossover::SinglePoint), Just(Crossover::TwoPoint), Just(Crossover::BarAligned), Just(Crossover::UniformByBar)]
+}
+
+fn total_duration(genes: &[Gene]) -> u16 {
+    genes.iter().map(|gene| gene.get_duration()).sum()
+}
+
+proptest! {
+
+    #[test]
+    fn random_chromosome_matches_melody_duration(melody in melody_strategy(), seed in any::<u64>()) {
+        let melody_duration = composer::calculate_melody_duration(&melody);
+        let chromosome = chromosome::generate_random_chromosome(&mut StdRng::seed_from_u64(seed), melody_duration);
+
+        prop_assert_eq!(chromosome.get_duration(), melody_duration);
+    }
+
+    #[test]
+    fn mutation_preserves_melody_duration(melody in melody_strategy(), seed in any::<u64>(), mutation_rate in 0.0..=1.0f64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let melody_duration = composer::calculate_melody_duration(&melody);
+        let mut chromosome = chromosome::generate_random_chromosome(&mut rng, melody_duration);
+
+        for _ in 0..8 {
+            prop_assert!(chromosome.mutate(&MutationConfig::uniform(mutation_rate), &mut rng).is_ok());
+            prop_assert_eq!(chromosome.get_duration(), melody_duration);
+        }
+    }
+
+    #[test]
+    fn mutation_preserves_duration_of_any_chromosome(mut chromosome in chromosome_strategy(), seed in any::<u64>(), mutation_rate in 0.0..=1.0f64) {
+        let duration = chromosome.get_duration();
+
+        prop_assert!(chromosome.mutate(&MutationConfig::uniform(mutation_rate), &mut StdRng::seed_from_u64(seed)).is_ok());
+        prop_assert_eq!(chromosome.get_duration(), duration);
+    }
+
+    #[test]
+    fn split_at_time_preserves_duration(chromosome in chromosome_strategy(), time in 0u16..600) {
+        let (first_segment, second_segment) = chromosome.split_at_time(time);
+
+        prop_assert_eq!(total_duration(&first_segment), time.min(chromosome.get_duration()));
+        prop_assert_eq!(total_duration(&first_segment) + total_duration(&second_segment), chromosome.get_duration());
+    }
+
+    #[test]
+    fn crossover_preserves_melody_duration(melody in melody_strategy(), seed in any::<u64>(), crossover in crossover_strategy()) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let melody_duration = composer::calculate_melody_duration(&melody);
+        let parent_one = chromosome::generate_random_chromosome(&mut rng, melody_duration);
+        let parent_two = chromosome::generate_random_chromosome(&mut rng, melody_duration);
+
+        let (child_one, child_two) = crossover::crossover_parents(&parent_one, &parent_two, crossover, &mut rng);
+
+        prop_assert_eq!(child_one.get_duration(), melody_duration);
+        prop_assert_eq!(child_two.get_duration(), melody_duration);
+    }
+
+    #[test]
+    fn crossover_selection_crosses_a_lone_parent_with_itself(melody in melody_strategy(), seed in any::<u64>(), crossover in crossover_strategy()) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let melody_duration = composer::calculate_melody_duration(&melody);
+        let parent = chromosome::generate_random_chromosome(&mut rng, melody_duration);
+
+        let offspring = composer::crossover_selection(&mut rng, std::slice::from_ref(&parent), 4, crossover);
+
+        prop_assert_eq!(offspring.len(), 4);
+        prop_assert!(offspring.iter().all(|child| ChordDistance::Timeline.distance(child, &parent) == 0.0));
+    }
+
+}
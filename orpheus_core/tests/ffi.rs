@@ -0,0 +1,70 @@
+use orpheus_core::ffi::{self, OrpheusChord, OrpheusNote};
+use std::{ffi::CStr, ptr};
+
+fn notes(melody: &[(u16, u16)]) -> Vec<OrpheusNote> {
+    melody.iter().map(|&(midi_note, duration)| OrpheusNote { midi_note, duration }).collect()
+}
+
+// Composes with a token cancelled up front, so valid melodies return straight away.
+fn compose(melody: &[(u16, u16)]) -> *mut ffi::OrpheusComposition {
+    let notes = notes(melody);
+
+    unsafe {
+        let token = ffi::orpheus_cancellation_token_new();
+        ffi::orpheus_cancellation_token_cancel(token);
+        let composition = ffi::orpheus_compose(notes.as_ptr(), notes.len(), None, ptr::null_mut(), token);
+        ffi::orpheus_cancellation_token_free(token);
+        composition
+    }
+}
+
+#[test]
+fn compose_returns_a_composition_for_a_valid_melody() {
+    let composition = compose(&[(60, 16), (64, 16), (67, 32)]);
+
+    assert!(!composition.is_null());
+    unsafe { ffi::orpheus_composition_free(composition) };
+}
+
+#[test]
+fn compose_rejects_invalid_melodies() {
+    assert!(compose(&[]).is_null());
+    assert!(compose(&[(60, 16), (64, 0), (67, 16)]).is_null());
+    assert!(compose(&[(60, u16::MAX), (64, 1)]).is_null());
+}
+
+fn fitness_breakdown_json(melody: &[(u16, u16)], chords: &[(u16, u16, u16)]) -> Option<String> {
+    let notes = notes(melody);
+    let chords: Vec<OrpheusChord> = chords.iter().map(|&(root_note, chord_type, duration)| OrpheusChord { root_note, chord_type, duration }).collect();
+
+    unsafe {
+        let json = ffi::orpheus_fitness_breakdown_json(notes.as_ptr(), notes.len(), chords.as_ptr(), chords.len());
+        if json.is_null() {
+            return None;
+        }
+
+        let string = CStr::from_ptr(json).to_str().unwrap().to_string();
+        ffi::orpheus_string_free(json);
+        Some(string)
+    }
+}
+
+#[test]
+fn fitness_breakdown_explains_a_matching_progression() {
+    let json = fitness_breakdown_json(&[(60, 16), (67, 16)], &[(0, 0, 16), (7, 0, 16)]).unwrap();
+
+    assert!(json.contains("\"notes\""));
+}
+
+#[test]
+fn fitness_breakdown_rejects_invalid_input() {
+    // A zero-length note, and a melody too long to measure.
+    assert_eq!(fitness_breakdown_json(&[(60, 16), (64, 0)], &[(0, 0, 16)]), None);
+    assert_eq!(fitness_breakdown_json(&[(60, u16::MAX), (64, 1)], &[(0, 0, 32)]), None);
+
+    // A chord that isn't valid, progressions shorter or longer than the melody, and one whose length overflows.
+    assert_eq!(fitness_breakdown_json(&[(60, 32)], &[(12, 0, 32)]), None);
+    assert_eq!(fitness_breakdown_json(&[(60, 32)], &[(0, 0, 16)]), None);
+    assert_eq!(fitness_breakdown_json(&[(60, 32)], &[(0, 0, 16), (7, 0, 32)]), None);
+    assert_eq!(fitness_breakdown_json(&[(60, 32)], &vec![(0, 0, 32); 2049]), None);
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use orpheus_core::{
+    composer::{self, chromosome::{self, fitness::{FitnessBreakdown, FitnessWeights, NoteFit}}, seeding},
+    melodies,
+};
+use rand::{SeedableRng, rngs::StdRng};
+
+#[test]
+fn breakdown_totals_calculated_fitness() {
+    let melody = melodies::amazing_grace();
+    let key = seeding::detect_key(&melody);
+    let weights = FitnessWeights::default();
+    let mut rng = StdRng::seed_from_u64(3);
+
+    for _ in 0..32 {
+        let mut chromosome = chromosome::generate_random_chromosome(&mut rng, composer::calculate_melody_duration(&melody));
+        chromosome.calculate_fitness(&melody, &key, &weights);
+        let breakdown = chromosome.fitness_breakdown(&melody, &key, &weights);
+
+        assert_eq!(breakdown.total, chromosome.get_fitness());
+        assert_eq!(
+            weights.chord_melody * breakdown.chord_melody + weights.boundary_alignment * breakdown.boundary_alignment,
+            chromosome.calculate_chord_melody_fitness(&melody, &key, &weights),
+        );
+        assert_eq!(breakdown.notes.len(), melody.len());
+        assert!(breakdown.clashes().all(|note| note.fitness == 0.0));
+
+        for term in [breakdown.chord_melody, breakdown.boundary_alignment, breakdown.chord_entropy, breakdown.duration_entropy] {
+            assert!((0.0..=1.0).contains(&term));
+        }
+    }
+}
+
+#[test]
+fn breakdown_round_trips_through_json() {
+    let melody = melodies::amazing_grace();
+    let chromosome = chromosome::generate_random_chromosome(&mut StdRng::seed_from_u64(4), composer::calculate_melody_duration(&melody));
+    let breakdown = chromosome.fitness_breakdown(&melody, &seeding::detect_key(&melody), &FitnessWeights::default());
+
+    let parsed: FitnessBreakdown = serde_json::from_str(&breakdown.to_json().unwrap()).unwrap();
+
+    assert_eq!(parsed, breakdown);
+    assert!(breakdown.notes.iter().all(|note| [NoteFit::ChordTone, NoteFit::ScaleTone, NoteFit::Clash].contains(&note.fit)));
+}
+
+#[test]
+fn zero_length_notes_go_with_the_chord_playing() {
+    let melody = [(60, 0), (64, 16), (67, 0), (67, 16), (72, 0)];
+    let chromosome = common::chromosome(&[(0, 0, 16), (7, 0, 16)], 0.0);
+
+    let breakdown = chromosome.fitness_breakdown(&melody, &seeding::detect_key(&melody), &FitnessWeights::default());
+    let gene_indices: Vec<usize> = breakdown.notes.iter().map(|note| note.gene_index).collect();
+
+    assert_eq!(gene_indices, [0, 0, 0, 1, 1]);
+}
+
+#[test]
+fn chromosomes_without_genes_score_no_notes() {
+    let melody = [(60, 0)];
+    let mut chromosome = chromosome::generate_chromosome(Vec::new());
+
+    let breakdown = chromosome.fitness_breakdown(&melody, &seeding::detect_key(&melody), &FitnessWeights::default());
+    chromosome.calculate_fitness(&melody, &seeding::detect_key(&melody), &FitnessWeights::default());
+
+    assert!(breakdown.notes.is_empty());
+    assert_eq!((breakdown.chord_melody, breakdown.boundary_alignment), (0.0, 0.0));
+}
+
+// Runs under whichever of the serial or parallel pool fitness the crate was built with.
+#[test]
+fn pool_fitness_matches_each_chromosome_fitness() {
+    let melody = melodies::amazing_grace();
+    let key = seeding::detect_key(&melody);
+    let weights = FitnessWeights::default();
+    let mut rng = StdRng::seed_from_u64(5);
+
+    let mut pool: Vec<_> = (0..64).map(|_| chromosome::generate_random_chromosome(&mut rng, composer::calculate_melody_duration(&melody))).collect();
+    let mut expected = pool.clone();
+
+    composer::calculate_pool_fitness(&mut pool, &melody, &key, &weights);
+
+    for chromosome in &mut expected {
+        chromosome.calculate_fitness(&melody, &key, &weights);
+    }
+
+    let fitnesses = |pool: &[chromosome::Chromosome]| pool.iter().map(|chromosome| chromosome.get_fitness()).collect::<Vec<f64>>();
+    assert_eq!(pool, expected);
+    assert_eq!(fitnesses(&pool), fitnesses(&expected));
+}
+
+// Chord and duration entropy fitness of a progression of (root note, chord type, duration) chords.
+fn entropy_fitness(chords: &[(u16, u16, u16)]) -> (f64, f64) {
+    let chromosome = common::chromosome(chords, 0.0);
+    let melody = [(60, chromosome.get_duration())];
+    let breakdown = chromosome.fitness_breakdown(&melody, &seeding::detect_key(&melody), &FitnessWeights::default());
+
+    (breakdown.chord_entropy, breakdown.duration_entropy)
+}
+
+#[test]
+fn chord_entropy_levels() {
+    let chords = |roots: &[u16]| roots.iter().map(|&root_note| (root_note, 0, 16)).collect::<Vec<_>>();
+
+    // Entropy of 1, 1.5, 0.65, 2, 0.47, 0 and 3 bits.
+    assert_eq!(entropy_fitness(&chords(&[0, 0, 7, 7])).0, 1.0);
+    assert_eq!(entropy_fitness(&chords(&[0, 0, 7, 9])).0, 0.75);
+    assert_eq!(entropy_fitness(&chords(&[0, 0, 0, 0, 0, 7])).0, 0.75);
+    assert_eq!(entropy_fitness(&chords(&[0, 7, 9, 5])).0, 0.5);
+    assert_eq!(entropy_fitness(&chords(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 7])).0, 0.5);
+    assert_eq!(entropy_fitness(&chords(&[0, 0, 0, 0])).0, 0.0);
+    assert_eq!(entropy_fitness(&chords(&[0, 1, 2, 3, 4, 5, 6, 7])).0, 0.0);
+}
+
+#[test]
+fn duration_entropy_levels() {
+    let chords = |durations: &[u16]| durations.iter().map(|&duration| (0, 0, duration)).collect::<Vec<_>>();
+
+    // Entropy of 2, 1.58, 3, 0 and 4 bits.
+    assert_eq!(entropy_fitness(&chords(&[2, 4, 8, 16])).1, 1.0);
+    assert_eq!(entropy_fitness(&chords(&[4, 8, 16])).1, 0.75);
+    assert_eq!(entropy_fitness(&chords(&[1, 2, 3, 4, 5, 6, 7, 8])).1, 0.75);
+    assert_eq!(entropy_fitness(&chords(&[8, 8, 8, 8])).1, 0.0);
+    assert_eq!(entropy_fitness(&chords(&(1..=16).collect::<Vec<u16>>())).1, 0.0);
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use common::gene_strategy;
+use orpheus_core::composer::chromosome::gene::{self, Gene, GeneError};
+use proptest::prelude::*;
+use std::collections::HashSet;
+
+proptest! {
+
+    #[test]
+    fn encoding_round_trips(gene in gene_strategy()) {
+        prop_assert_eq!(Gene::decode(gene.encode()), Ok(gene));
+    }
+
+    #[test]
+    fn equal_genes_hash_equally(one in gene_strategy(), two in gene_strategy()) {
+        let set: HashSet<Gene> = [one, two].into_iter().collect();
+
+        prop_assert_eq!(one == two, one.encode() == two.encode());
+        prop_assert_eq!(set.len(), if one == two { 1 } else { 2 });
+    }
+}
+
+#[test]
+fn invalid_fields_are_rejected() {
+    assert_eq!(gene::generate_gene(gene::MAX_ROOT_NOTE + 1, 0, 16), Err(GeneError::InvalidRootNote(gene::MAX_ROOT_NOTE + 1)));
+    assert_eq!(gene::generate_gene(0, gene::MAX_CHORD_TYPE + 1, 16), Err(GeneError::InvalidChordType(gene::MAX_CHORD_TYPE + 1)));
+    assert_eq!(gene::generate_gene(0, 0, 0), Err(GeneError::InvalidDuration(0)));
+    assert_eq!(gene::generate_gene(0, 0, gene::MAX_DURATION + 1), Err(GeneError::InvalidDuration(gene::MAX_DURATION + 1)));
+
+    let mut gene = gene::generate_gene(0, 0, 16).unwrap();
+    assert_eq!(gene.set_root_note(12), Err(GeneError::InvalidRootNote(12)));
+    assert_eq!(gene.set_duration(0), Err(GeneError::InvalidDuration(0)));
+    assert_eq!(gene.with_duration(33), Err(GeneError::InvalidDuration(33)));
+    assert_eq!(gene, gene::generate_gene(0, 0, 16).unwrap());
+}
+
+#[test]
+fn encodings_with_unused_bits_are_rejected() {
+    let encoding = 1 << 10;
+    assert_eq!(Gene::decode(encoding), Err(GeneError::InvalidEncoding(encoding)));
+
+    // Root note 12 fits the root note bits but is not a pitch class.
+    assert_eq!(Gene::decode(12 << 6), Err(GeneError::InvalidRootNote(12)));
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::melody_strategy;
+use orpheus_core::composer::{self, chromosome::{self, fitness::FitnessWeights}, harmonizer, seeding};
+use proptest::prelude::*;
+use rand::{SeedableRng, rngs::StdRng};
+
+// Rounding differs between summing the search's per-note shares and the chromosome's means.
+const TOLERANCE: f64 = 1e-9;
+
+proptest! {
+
+    #[test]
+    fn harmonization_is_valid_and_scored_by_chord_melody_fitness(melody in melody_strategy()) {
+        let key = seeding::detect_key(&melody);
+        let weights = FitnessWeights::default();
+        let harmonization = harmonizer::harmonize(&melody, &key, &weights).unwrap();
+
+        prop_assert_eq!(harmonization.chromosome.get_duration(), composer::calculate_melody_duration(&melody));
+        prop_assert!((harmonization.score - harmonization.chromosome.calculate_chord_melody_fitness(&melody, &key, &weights)).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn no_random_chromosome_beats_harmonization(melody in melody_strategy(), seed in any::<u64>()) {
+        let key = seeding::detect_key(&melody);
+        let weights = FitnessWeights::default();
+        let harmonization = harmonizer::harmonize(&melody, &key, &weights).unwrap();
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for _ in 0..16 {
+            let chromosome = chromosome::generate_random_chromosome(&mut rng, composer::calculate_melody_duration(&melody));
+            prop_assert!(chromosome.calculate_chord_melody_fitness(&melody, &key, &weights) <= harmonization.score + TOLERANCE);
+        }
+    }
+}
+
+#[test]
+fn notes_at_the_start_with_no_length_are_scored_against_the_first_chord() {
+    // A D♭ major arpeggio with no length, then a C for a bar.
+    let melody = [(61, 0), (65, 0), (68, 0), (60, 16)];
+    let key = seeding::detect_key(&melody);
+    let weights = FitnessWeights::default();
+    let harmonization = harmonizer::harmonize(&melody, &key, &weights).unwrap();
+
+    assert!((harmonization.score - harmonization.chromosome.calculate_chord_melody_fitness(&melody, &key, &weights)).abs() < TOLERANCE);
+}
@@ -0,0 +1,82 @@
+use orpheus_core::{
+    composer::{
+        chromosome::Chromosome,
+        config::Config,
+        constraints::Constraint,
+        islands::{IslandModel, MigrationConfig},
+    },
+    melodies,
+    theory::{Chord, ChordQuality, PitchClass},
+};
+
+const MIGRATION: MigrationConfig = MigrationConfig { interval: 0, migrant_count: 2 };
+
+fn config(seed: u64) -> Config {
+    Config { initial_pool_size: 20, selection_pool_size: 8, offspring_target: 18, elite_pool_size: 2, seed: Some(seed), ..Config::default() }
+}
+
+fn fittest(island_model: &IslandModel, index: usize) -> Vec<Chromosome> {
+    island_model.islands()[index].population()[..MIGRATION.migrant_count].to_vec()
+}
+
+#[test]
+fn fittest_chromosomes_migrate_round_the_ring() {
+    let mut island_model = IslandModel::new(&melodies::amazing_grace(), &[config(1), config(2), config(3)], MIGRATION).unwrap();
+    island_model.step();
+
+    let emigrants: Vec<Vec<Chromosome>> = (0..3).map(|index| fittest(&island_model, index)).collect();
+    island_model.migrate();
+
+    for (index, migrants) in emigrants.iter().enumerate() {
+        let destination = island_model.islands()[(index + 1) % 3].population();
+
+        assert_eq!(destination.len(), 20);
+        assert!(migrants.iter().all(|migrant| destination.contains(migrant)));
+    }
+}
+
+#[test]
+fn migrants_meet_the_receiving_island_constraints() {
+    let a_minor = Chord::new(PitchClass::new(9).unwrap(), ChordQuality::Minor);
+    let locked = Config {
+        constraints: vec![Constraint::LockChord { start: 0, end: 32, chord: a_minor }],
+        ..config(2)
+    };
+    let mut island_model = IslandModel::new(&melodies::amazing_grace(), &[config(1), locked.clone()], MIGRATION).unwrap();
+    let starts_with_a_minor = |chromosome: &Chromosome| chromosome.get_genes()[0].chord() == a_minor;
+
+    assert!(!fittest(&island_model, 0).iter().all(starts_with_a_minor));
+
+    island_model.migrate();
+
+    assert!(island_model.islands()[1].population().iter().all(starts_with_a_minor));
+}
+
+#[test]
+fn migration_happens_every_interval() {
+    let melody = melodies::amazing_grace();
+    let configs = [config(1), config(2)];
+    let mut island_model = IslandModel::new(&melody, &configs, MigrationConfig { interval: 2, migrant_count: 2 }).unwrap();
+    let mut manual = IslandModel::new(&melody, &configs, MIGRATION).unwrap();
+
+    island_model.step();
+    manual.step();
+
+    assert_eq!(island_model.population(), manual.population());
+
+    island_model.step();
+    manual.step();
+    manual.migrate();
+
+    for (island, manual_island) in island_model.islands().iter().zip(manual.islands()) {
+        assert_eq!(island.population(), manual_island.population());
+    }
+}
+
+#[test]
+fn candidates_follow_the_island_config() {
+    let config = Config { result_count: 3, ..config(1) };
+    let island_model = IslandModel::new(&melodies::amazing_grace(), &[config.clone(), config], MIGRATION).unwrap();
+
+    assert_eq!(island_model.candidates().len(), 3);
+}
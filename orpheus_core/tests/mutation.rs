@@ -0,0 +1,101 @@
+mod common;
+
+use common::chromosome_strategy;
+use orpheus_core::{
+    composer::{BEAT_DURATION, chromosome::{Chromosome, gene::Gene}, config::MutationConfig},
+    theory::{ChordQuality, Interval},
+};
+use proptest::prelude::*;
+use rand::{SeedableRng, rngs::StdRng};
+
+// Mutates with only the operator set by `enable` firing, on every roll.
+fn mutate_with(chromosome: &Chromosome, seed: u64, enable: fn(&mut MutationConfig)) -> Vec<Gene> {
+    let mut mutation_config = MutationConfig::uniform(0.0);
+    enable(&mut mutation_config);
+
+    let mut mutated = chromosome.clone();
+    mutated.mutate(&mutation_config, &mut StdRng::seed_from_u64(seed)).unwrap();
+    mutated.get_genes().to_vec()
+}
+
+// Semiquaver every gene starts on.
+fn starts(genes: &[Gene]) -> Vec<u16> {
+    genes.iter().scan(0, |time, gene| {
+        let start = *time;
+        *time += gene.get_duration();
+        Some(start)
+    }).collect()
+}
+
+proptest! {
+
+    #[test]
+    fn transpose_moves_the_root_by_a_fifth_or_fourth(chromosome in chromosome_strategy(), seed in any::<u64>()) {
+        let mutated = mutate_with(&chromosome, seed, |config| config.transpose_rate = 1.0);
+
+        for (original, transposed) in chromosome.get_genes().iter().zip(&mutated) {
+            let interval = original.root.interval_to(transposed.root);
+            prop_assert!(interval == Interval::PERFECT_FIFTH || interval == Interval::PERFECT_FOURTH);
+            prop_assert_eq!((transposed.quality, transposed.duration), (original.quality, original.duration));
+        }
+    }
+
+    #[test]
+    fn substitution_gives_the_relative_or_tritone_chord(chromosome in chromosome_strategy(), seed in any::<u64>()) {
+        let mutated = mutate_with(&chromosome, seed, |config| config.substitute_rate = 1.0);
+
+        for (original, substituted) in chromosome.get_genes().iter().zip(&mutated) {
+            let interval = original.root.interval_to(substituted.root);
+
+            match (original.quality, substituted.quality) {
+                // Relative minor.
+                (ChordQuality::Major, ChordQuality::Minor) => prop_assert_eq!(interval, Interval::MAJOR_SIXTH),
+                // Tritone substitution.
+                (ChordQuality::Major, ChordQuality::Major) => prop_assert_eq!(interval, Interval::TRITONE),
+                // Relative major.
+                (ChordQuality::Minor, ChordQuality::Major) => prop_assert_eq!(interval, Interval::MINOR_THIRD),
+                (ChordQuality::Minor, ChordQuality::Minor) => prop_assert!(false, "a minor chord always becomes its relative major"),
+            }
+            prop_assert_eq!(substituted.duration, original.duration);
+        }
+    }
+
+    #[test]
+    fn split_keeps_the_chord_in_the_first_half(chromosome in chromosome_strategy(), seed in any::<u64>()) {
+        let mutated = mutate_with(&chromosome, seed, |config| config.split_rate = 1.0);
+        let mutated_starts = starts(&mutated);
+
+        for (original, start) in chromosome.get_genes().iter().zip(starts(chromosome.get_genes())) {
+            let index = mutated_starts.iter().position(|&mutated_start| mutated_start == start).unwrap();
+            prop_assert_eq!(mutated[index].chord(), original.chord());
+
+            let expected_duration = if original.get_duration() > 1 { original.get_duration() / 2 } else { 1 };
+            prop_assert_eq!(mutated[index].get_duration(), expected_duration);
+        }
+    }
+
+    #[test]
+    fn merge_keeps_the_earlier_chord(chromosome in chromosome_strategy(), seed in any::<u64>()) {
+        let mutated = mutate_with(&chromosome, seed, |config| config.merge_rate = 1.0);
+        let original_starts = starts(chromosome.get_genes());
+
+        prop_assert!(mutated.len() <= chromosome.get_genes().len());
+
+        for (gene, start) in mutated.iter().zip(starts(&mutated)) {
+            let index = original_starts.iter().position(|&original_start| original_start == start).unwrap();
+            prop_assert_eq!(gene.chord(), chromosome.get_genes()[index].chord());
+        }
+    }
+
+    #[test]
+    fn shifted_boundaries_move_by_one_beat(chromosome in chromosome_strategy(), seed in any::<u64>()) {
+        let mutated = mutate_with(&chromosome, seed, |config| config.shift_boundary_rate = 1.0);
+
+        prop_assert_eq!(mutated.len(), chromosome.get_genes().len());
+
+        for ((original, shifted), (original_start, shifted_start)) in chromosome.get_genes().iter().zip(&mutated).zip(starts(chromosome.get_genes()).into_iter().zip(starts(&mutated))) {
+            prop_assert_eq!(shifted.chord(), original.chord());
+            prop_assert!(original_start.abs_diff(shifted_start) == 0 || original_start.abs_diff(shifted_start) == BEAT_DURATION);
+        }
+    }
+}
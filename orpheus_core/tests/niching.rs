@@ -0,0 +1,90 @@
+mod common;
+
+use common::chromosome;
+use orpheus_core::composer::{
+    chromosome::Chromosome,
+    niching,
+    results::ChordDistance,
+};
+
+fn fitnesses(chromosome_pool: &[Chromosome]) -> Vec<f64> {
+    chromosome_pool.iter().map(|chromosome| chromosome.get_fitness()).collect()
+}
+
+#[test]
+fn only_exact_duplicates_are_removed() {
+    let mut pool = vec![
+        chromosome(&[(0, 0, 16), (7, 0, 16)], 0.9),
+        chromosome(&[(0, 0, 16), (7, 0, 16)], 0.5),
+        // Sounds the same but is split differently.
+        chromosome(&[(0, 0, 8), (0, 0, 8), (7, 0, 16)], 0.8),
+        chromosome(&[(0, 0, 16), (7, 0, 15), (7, 0, 1)], 0.7),
+        chromosome(&[(0, 0, 16), (7, 0, 16)], 0.4),
+        chromosome(&[(9, 1, 32)], 0.6),
+    ];
+
+    niching::remove_duplicates(&mut pool);
+
+    // The first copy of each progression is kept, in order.
+    assert_eq!(fitnesses(&pool), [0.9, 0.8, 0.7, 0.6]);
+}
+
+#[test]
+fn clustered_chromosomes_share_their_fitness() {
+    let mut pool = vec![
+        chromosome(&[(0, 0, 16), (7, 0, 16)], 0.8),
+        chromosome(&[(0, 0, 8), (0, 0, 8), (7, 0, 16)], 0.8),
+        chromosome(&[(0, 0, 16), (7, 0, 12), (5, 0, 4)], 0.8),
+        chromosome(&[(9, 1, 32)], 0.8),
+        chromosome(&[(5, 0, 32)], -0.4),
+    ];
+
+    niching::share_fitness(&mut pool, 0.25, ChordDistance::Timeline);
+
+    let shared = fitnesses(&pool);
+
+    // The first two are identical and the third is half a radius from both: niche counts of 2.5, 2.5 and 2.
+    assert_eq!(shared[0], 0.8 / 2.5);
+    assert_eq!(shared[1], 0.8 / 2.5);
+    assert_eq!(shared[2], 0.8 / 2.0);
+
+    // Chromosomes alone in their niche keep their fitness.
+    assert_eq!(shared[3], 0.8);
+    assert_eq!(shared[4], -0.4);
+}
+
+#[test]
+fn crowding_pushes_negative_fitness_down() {
+    let mut pool = vec![
+        chromosome(&[(0, 0, 16), (7, 0, 16)], -0.4),
+        chromosome(&[(0, 0, 16), (7, 0, 16)], -0.4),
+    ];
+
+    niching::share_fitness(&mut pool, 0.25, ChordDistance::Timeline);
+
+    assert_eq!(fitnesses(&pool), [-0.8, -0.8]);
+}
+
+#[test]
+fn niches_hold_at_least_the_chromosome_itself() {
+    let mut pool = vec![
+        chromosome(&[(0, 0, 16), (7, 0, 16)], 0.8),
+        chromosome(&[(0, 0, 16), (7, 0, 16)], -0.4),
+    ];
+
+    niching::share_fitness(&mut pool, 0.0, ChordDistance::Timeline);
+
+    assert_eq!(fitnesses(&pool), [0.8, -0.4]);
+}
+
+#[test]
+fn pool_diversity_is_the_mean_pairwise_distance() {
+    let pool = vec![
+        chromosome(&[(0, 0, 16), (7, 0, 16)], 0.0),
+        chromosome(&[(0, 0, 16), (9, 1, 16)], 0.0),
+        chromosome(&[(0, 0, 32)], 0.0),
+    ];
+
+    assert_eq!(niching::pool_diversity(&pool, ChordDistance::Timeline), 0.5);
+    assert_eq!(niching::pool_diversity(&pool[..1], ChordDistance::Timeline), 0.0);
+}
@@ -0,0 +1,70 @@
+use orpheus_core::{
+    composer::{
+        config::Config,
+        genetic_composer::GeneticComposer,
+        pareto::{self, Objective, Optimisation},
+    },
+    melodies,
+};
+
+fn points() -> Vec<Vec<f64>> {
+    vec![
+        vec![1.0, 0.0],
+        vec![0.0, 1.0],
+        vec![0.5, 0.5],
+        vec![0.4, 0.4],
+        vec![0.1, 0.1],
+        vec![0.5, 0.5],
+    ]
+}
+
+#[test]
+fn sorts_points_into_non_dominated_fronts() {
+    let mut fronts = pareto::non_dominated_fronts(&points());
+    fronts.iter_mut().for_each(|front| front.sort());
+
+    assert_eq!(fronts, vec![vec![0, 1, 2, 5], vec![3], vec![4]]);
+}
+
+#[test]
+fn keeps_extremes_of_the_last_front() {
+    let points = points();
+    let front = &pareto::non_dominated_fronts(&points)[0];
+    let distances = pareto::crowding_distances(front, &points);
+
+    for (&index, distance) in front.iter().zip(&distances) {
+        assert_eq!(distance.is_infinite(), index == 0 || index == 1);
+    }
+
+    let mut survivors = pareto::select_survivors(&points, 2);
+    survivors.sort();
+    assert_eq!(survivors, vec![0, 1]);
+}
+
+#[test]
+fn nsga2_returns_a_non_dominated_front() {
+    let objectives = vec![Objective::ChordMelody, Objective::ChordEntropy, Objective::DurationEntropy];
+    let config = Config { seed: Some(11), optimisation: Optimisation::Nsga2 { objectives: objectives.clone() }, ..Config::default() };
+    let mut genetic_composer = GeneticComposer::new(&melodies::amazing_grace(), &config).unwrap();
+
+    for _ in 0..20 {
+        genetic_composer.step();
+    }
+
+    assert_eq!(genetic_composer.population().len(), config.initial_pool_size);
+
+    let front = genetic_composer.pareto_front();
+    assert!(!front.is_empty());
+
+    for candidate in &front {
+        let values: Vec<f64> = objectives.iter().map(|&objective| candidate.value(objective).unwrap()).collect();
+        let dominated = front.iter().any(|other| {
+            let other_values: Vec<f64> = objectives.iter().map(|&objective| other.value(objective).unwrap()).collect();
+            pareto::dominates(&other_values, &values)
+        });
+        assert!(!dominated);
+    }
+
+    let front_chromosomes: Vec<_> = front.iter().map(|candidate| &candidate.chromosome).collect();
+    assert!(genetic_composer.candidates().iter().all(|candidate| front_chromosomes.contains(&&candidate.chromosome)));
+}
@@ -0,0 +1,87 @@
+use orpheus_core::{
+    composer::{chromosome::Chromosome, config::Config, genetic_composer::GeneticComposer, ratings::{self, Rating}, results::ChordDistance},
+    melodies,
+};
+
+fn seeded_config() -> Config {
+    Config { seed: Some(7), ..Config::default() }
+}
+
+#[test]
+fn liked_progression_gains_rating_weight() {
+    let config = seeded_config();
+    let mut genetic_composer = GeneticComposer::new(&melodies::amazing_grace(), &config).unwrap();
+    let liked = genetic_composer.population()[config.initial_pool_size - 1].clone();
+
+    genetic_composer.rate(Rating::new(&liked, ratings::LIKE));
+
+    let rated = genetic_composer.population().iter().find(|chromosome| **chromosome == liked).unwrap();
+    assert!((rated.get_fitness() - (liked.get_fitness() + config.fitness_weights.rating)).abs() < 1e-12);
+}
+
+#[test]
+fn segment_rating_only_compares_rated_range() {
+    let config = seeded_config();
+    let genetic_composer = GeneticComposer::new(&melodies::amazing_grace(), &config).unwrap();
+    let (first, second) = (&genetic_composer.population()[0], &genetic_composer.population()[1]);
+
+    let first_half = Rating::for_segment(first, 0..first.get_duration() / 2, ratings::DISLIKE);
+    let first_timeline = Rating::new(first, ratings::LIKE).chord_timeline;
+
+    assert_eq!(first_half.similarity(&first_timeline), 1.0);
+    assert_eq!(ratings::rating_fitness(std::slice::from_ref(&first_half), first), ratings::DISLIKE);
+
+    // The second chromosome is disliked in proportion to the semiquavers of the first half it shares.
+    let rated_timeline = &first_half.chord_timeline;
+    let matching_semiquavers = ChordDistance::Timeline.profile(second).iter().zip(rated_timeline).filter(|(chord, rated_chord)| chord == rated_chord).count();
+    let expected = ratings::DISLIKE * matching_semiquavers as f64 / rated_timeline.len() as f64;
+
+    assert!(matching_semiquavers < rated_timeline.len());
+    assert_eq!(ratings::rating_fitness(&[first_half], second), expected);
+}
+
+// Mean similarity of the population to the rated progression.
+fn similarity(genetic_composer: &GeneticComposer, rating: &Rating) -> f64 {
+    let population = genetic_composer.population();
+    population.iter().map(|chromosome| rating.similarity(&ChordDistance::Timeline.profile(chromosome))).sum::<f64>() / population.len() as f64
+}
+
+// Whether a chromosome plays the liked chords for at least three quarters of the melody.
+fn is_close_variant(chromosome: &Chromosome, liked: &Chromosome) -> bool {
+    ChordDistance::Timeline.distance(chromosome, liked) < 0.25
+}
+
+#[test]
+fn liked_progressions_spread_through_later_generations() {
+    let mut config = seeded_config();
+    config.fitness_weights.rating = 1.0;
+
+    let mut unrated = GeneticComposer::new(&melodies::amazing_grace(), &config).unwrap();
+    let mut rated = unrated.clone();
+    let liked = unrated.population()[config.initial_pool_size - 1].clone();
+    let rating = Rating::new(&liked, ratings::LIKE);
+
+    rated.rate(rating.clone());
+
+    for _ in 0..20 {
+        unrated.step();
+        rated.step();
+    }
+
+    assert!(is_close_variant(rated.best(), &liked));
+    assert!(!unrated.population().iter().any(|chromosome| is_close_variant(chromosome, &liked)));
+    assert!(similarity(&rated, &rating) > similarity(&unrated, &rating));
+}
+
+#[test]
+fn ratings_survive_checkpoints() {
+    let mut genetic_composer = GeneticComposer::new(&melodies::amazing_grace(), &seeded_config()).unwrap();
+    let liked = genetic_composer.best().clone();
+    genetic_composer.rate(Rating::new(&liked, ratings::LIKE));
+
+    let mut checkpoint = Vec::new();
+    genetic_composer.write_checkpoint(&mut checkpoint).unwrap();
+    let resumed = GeneticComposer::read_checkpoint(checkpoint.as_slice()).unwrap();
+
+    assert_eq!(resumed.ratings(), genetic_composer.ratings());
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::chromosome;
+use orpheus_core::composer::results::{self, Character, ChordDistance};
+
+#[test]
+fn timeline_distance_counts_semiquavers_with_different_chords() {
+    let c_g = chromosome(&[(0, 0, 16), (7, 0, 16)], 0.0);
+    let c_c_g = chromosome(&[(0, 0, 8), (0, 0, 8), (7, 0, 16)], 0.0);
+    let c_am = chromosome(&[(0, 0, 16), (9, 1, 16)], 0.0);
+    let c_g_short = chromosome(&[(0, 0, 16), (7, 0, 8)], 0.0);
+
+    // The same chords split differently sound the same.
+    assert_eq!(ChordDistance::Timeline.distance(&c_g, &c_c_g), 0.0);
+    assert_eq!(ChordDistance::Timeline.distance(&c_g, &c_am), 0.5);
+    assert_eq!(ChordDistance::Timeline.distance(&c_g, &c_g_short), 0.25);
+    assert_eq!(ChordDistance::Timeline.distance(&c_g, &c_g), 0.0);
+}
+
+#[test]
+fn sequence_distance_is_the_edit_distance_ignoring_durations() {
+    let c_g = chromosome(&[(0, 0, 16), (7, 0, 16)], 0.0);
+    let c_g_long = chromosome(&[(0, 0, 24), (7, 0, 8)], 0.0);
+    let c_c_g = chromosome(&[(0, 0, 8), (0, 0, 8), (7, 0, 16)], 0.0);
+    let am_f = chromosome(&[(9, 1, 16), (5, 0, 16)], 0.0);
+
+    assert_eq!(ChordDistance::Sequence.distance(&c_g, &c_g_long), 0.0);
+    assert_eq!(ChordDistance::Sequence.distance(&c_g, &c_c_g), 1.0 / 3.0);
+    assert_eq!(ChordDistance::Sequence.distance(&c_g, &am_f), 1.0);
+}
+
+#[test]
+fn diverse_results_are_distinct_and_far_enough_apart() {
+    let pool = vec![
+        chromosome(&[(0, 0, 16), (7, 0, 16)], 0.9),
+        chromosome(&[(0, 0, 16), (7, 0, 12), (5, 0, 4)], 0.85),
+        chromosome(&[(0, 0, 8), (0, 0, 8), (7, 0, 16)], 0.8),
+        chromosome(&[(0, 0, 16), (9, 1, 16)], 0.7),
+        chromosome(&[(5, 0, 16), (7, 0, 16)], 0.6),
+        chromosome(&[(9, 1, 32)], 0.5),
+    ];
+
+    let candidates = results::select_diverse_results(&pool, 3, 0.25, ChordDistance::Timeline);
+
+    assert_eq!(candidates.len(), 3);
+    assert_eq!(candidates[0].chromosome.get_fitness(), 0.9);
+
+    for (index, candidate) in candidates.iter().enumerate() {
+        for other in &candidates[index + 1..] {
+            assert!(ChordDistance::Timeline.distance(&candidate.chromosome, &other.chromosome) >= 0.25);
+        }
+    }
+
+    // The near copies of the best progression are skipped for the next fittest distant ones.
+    let fitnesses: Vec<f64> = candidates.iter().map(|candidate| candidate.chromosome.get_fitness()).collect();
+    assert_eq!(fitnesses, [0.9, 0.7, 0.6]);
+}
+
+#[test]
+fn uniform_pools_fill_up_with_the_fittest_distinct_chromosomes() {
+    let pool = vec![
+        chromosome(&[(0, 0, 16), (7, 0, 16)], 0.9),
+        chromosome(&[(0, 0, 8), (0, 0, 8), (7, 0, 16)], 0.8),
+        chromosome(&[(0, 0, 16), (7, 0, 12), (5, 0, 4)], 0.7),
+        chromosome(&[(0, 0, 16), (7, 0, 10), (5, 0, 6)], 0.6),
+    ];
+
+    let candidates = results::select_diverse_results(&pool, 3, 0.25, ChordDistance::Timeline);
+    let fitnesses: Vec<f64> = candidates.iter().map(|candidate| candidate.chromosome.get_fitness()).collect();
+
+    // The second chromosome sounds the same as the first, so it is never picked.
+    assert_eq!(fitnesses, [0.9, 0.7, 0.6]);
+}
+
+#[test]
+fn candidates_are_labelled_by_distance_from_the_best() {
+    let pool = vec![
+        chromosome(&[(0, 0, 16), (7, 0, 16)], 0.9),
+        chromosome(&[(0, 0, 16), (7, 0, 8), (5, 0, 8)], 0.8),
+        chromosome(&[(0, 0, 16), (9, 1, 16)], 0.7),
+        chromosome(&[(9, 1, 32)], 0.6),
+    ];
+
+    let candidates = results::select_diverse_results(&pool, 4, 0.0, ChordDistance::Timeline);
+
+    let labels: Vec<(f64, Character)> = candidates.iter().map(|candidate| (candidate.distance_from_best, candidate.character)).collect();
+    assert_eq!(labels, [(0.0, Character::Safe), (0.25, Character::Balanced), (0.5, Character::Adventurous), (1.0, Character::Adventurous)]);
+
+    let nearest: Vec<f64> = candidates.iter().map(|candidate| candidate.distance_from_nearest).collect();
+    assert_eq!(nearest, [0.25, 0.25, 0.5, 0.5]);
+}
@@ -0,0 +1,44 @@
+use orpheus_core::{
+    composer::{self, config::Config, seeding::{self, ProgressionError, SeedingConfig}},
+    melodies,
+    theory::{ChordQuality, Key, PitchClass},
+};
+use rand::{SeedableRng, rngs::StdRng};
+
+#[test]
+fn parses_typed_progressions() {
+    let genes = seeding::parse_progression("C | G:8 Am:8 | F#m Bb").unwrap();
+    let chords: Vec<(u16, u16, u16)> = genes.iter().map(|gene| (gene.get_root_note(), gene.get_chord_type(), gene.get_duration())).collect();
+
+    assert_eq!(chords, vec![(0, 0, 16), (7, 0, 8), (9, 1, 8), (6, 1, 16), (10, 0, 16)]);
+}
+
+#[test]
+fn rejects_invalid_progressions() {
+    assert_eq!(seeding::parse_progression(" | "), Err(ProgressionError::Empty));
+    assert_eq!(seeding::parse_progression("C H"), Err(ProgressionError::InvalidChord("H".to_string())));
+    assert_eq!(seeding::parse_progression("C:40"), Err(ProgressionError::InvalidDuration("C:40".to_string())));
+}
+
+#[test]
+fn detects_key_of_scale() {
+    let g_major_scale: Vec<(u16, u16)> = [67, 69, 71, 72, 74, 76, 78, 79].iter().map(|&note| (note, 4)).collect();
+
+    assert_eq!(seeding::detect_key(&g_major_scale), Key::new(PitchClass::new(7).unwrap(), ChordQuality::Major));
+}
+
+#[test]
+fn seeded_pool_matches_melody_duration() {
+    let melody = melodies::amazing_grace();
+    let melody_duration = composer::calculate_melody_duration(&melody);
+    let config = Config {
+        seeding: SeedingConfig { ratio: 0.5, progressions: vec!["C F G C".to_string()], ..SeedingConfig::default() },
+        ..Config::default()
+    };
+
+    let pool = seeding::generate_seeded_chromosome_pool(&mut StdRng::seed_from_u64(0), &config, &melody);
+
+    assert_eq!(pool.len(), config.initial_pool_size);
+    assert!(pool.iter().all(|chromosome| chromosome.get_duration() == melody_duration));
+    assert_eq!(pool[0].get_genes()[1].get_chord(), 10);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{key, pitch_class};
+use orpheus_core::theory::{
+    Chord,
+    ChordQuality,
+    Key,
+    spelling::{Accidentals, ChordSymbolStyle, MinorSuffix},
+};
+
+fn symbol(root_note: u16, quality: ChordQuality, key: &Key, style: &ChordSymbolStyle) -> String {
+    Chord::new(pitch_class(root_note), quality).symbol(key, style)
+}
+
+#[test]
+fn roots_are_spelt_for_the_key() {
+    let style = ChordSymbolStyle::default();
+
+    // IV of A♭ major against iii of A major.
+    assert_eq!(symbol(1, ChordQuality::Major, &key(8, ChordQuality::Major), &style), "D♭");
+    assert_eq!(symbol(1, ChordQuality::Minor, &key(9, ChordQuality::Major), &style), "C♯m");
+
+    // Outside the key: ♭VII in D major, the dominant of the relative minor in F major.
+    assert_eq!(symbol(0, ChordQuality::Major, &key(2, ChordQuality::Major), &style), "C");
+    assert_eq!(symbol(4, ChordQuality::Major, &key(5, ChordQuality::Major), &style), "E");
+    assert_eq!(symbol(8, ChordQuality::Major, &key(0, ChordQuality::Major), &style), "A♭");
+    assert_eq!(symbol(10, ChordQuality::Major, &key(4, ChordQuality::Major), &style), "A♯");
+
+    // Major dominant of a minor key, whose raised seventh would otherwise be a double sharp.
+    assert_eq!(symbol(3, ChordQuality::Major, &key(8, ChordQuality::Minor), &style), "D♯");
+    assert_eq!(symbol(7, ChordQuality::Major, &key(8, ChordQuality::Minor), &style), "G");
+}
+
+#[test]
+fn styles_change_accidentals_and_suffixes() {
+    let e_flat_major = key(3, ChordQuality::Major);
+    let ascii_dash = ChordSymbolStyle { accidentals: Accidentals::Ascii, minor_suffix: MinorSuffix::Dash };
+    let unicode_min = ChordSymbolStyle { accidentals: Accidentals::Unicode, minor_suffix: MinorSuffix::Min };
+
+    assert_eq!(symbol(10, ChordQuality::Major, &e_flat_major, &ascii_dash), "Bb");
+    assert_eq!(symbol(0, ChordQuality::Minor, &e_flat_major, &ascii_dash), "C-");
+    assert_eq!(symbol(5, ChordQuality::Minor, &e_flat_major, &unicode_min), "Fmin");
+    assert_eq!(e_flat_major.name(Accidentals::Unicode), "E♭ major");
+    assert_eq!(key(6, ChordQuality::Minor).name(Accidentals::Ascii), "F# minor");
+    assert_eq!(ChordSymbolStyle::default().minor_suffix, MinorSuffix::Lowercase);
+}
+
+#[test]
+fn keys_with_six_accidentals_take_sharps() {
+    assert_eq!(key(6, ChordQuality::Major).name(Accidentals::Unicode), "F♯ major");
+    assert_eq!(key(3, ChordQuality::Minor).name(Accidentals::Unicode), "D♯ minor");
+    assert_eq!(key(1, ChordQuality::Major).name(Accidentals::Unicode), "D♭ major");
+    assert_eq!(key(1, ChordQuality::Minor).name(Accidentals::Unicode), "C♯ minor");
+}
+
+#[test]
+fn bass_notes_are_spelt_as_chord_tones() {
+    let style = ChordSymbolStyle::default();
+    let c_major = key(0, ChordQuality::Major);
+
+    assert_eq!(Chord::new(pitch_class(0), ChordQuality::Major).slash_symbol(pitch_class(4), &c_major, &style), "C/E");
+    assert_eq!(Chord::new(pitch_class(4), ChordQuality::Major).slash_symbol(pitch_class(8), &c_major, &style), "E/G♯");
+    assert_eq!(Chord::new(pitch_class(5), ChordQuality::Major).slash_symbol(pitch_class(7), &c_major, &style), "F/G");
+    assert_eq!(Chord::new(pitch_class(7), ChordQuality::Major).slash_symbol(pitch_class(7), &c_major, &style), "G");
+}
+
+#[test]
+fn every_key_spells_every_chord_within_a_double_accidental() {
+    for key in Key::all() {
+        for index in 0..Chord::COUNT as u16 {
+            let chord = Chord::from_index(index).unwrap();
+            let spellings = chord.spell(&key);
+
+            assert_eq!(spellings.map(|spelling| spelling.pitch_class()), chord.pitch_classes());
+            assert!(spellings[0].accidental.abs() <= 1, "{chord:?} in {key:?} has root {:?}", spellings[0]);
+            assert!(spellings.iter().all(|spelling| spelling.accidental.abs() <= 2), "{chord:?} in {key:?} is spelt {spellings:?}");
+        }
+    }
+}
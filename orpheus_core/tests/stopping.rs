@@ -0,0 +1,103 @@
+use orpheus_core::{
+    composer::{
+        config::Config,
+        genetic_composer::GeneticComposer,
+        progress::{CancellationToken, Control},
+        stopping::{StopReason, StoppingCriteria, StoppingMonitor},
+    },
+    melodies,
+};
+use std::time::Duration;
+
+fn criteria() -> StoppingCriteria {
+    StoppingCriteria {
+        max_generations: 100,
+        stagnation_generations: None,
+        target_fitness: None,
+        time_budget: None,
+        minimum_diversity: None,
+    }
+}
+
+// Checks every generation from `start_generation` with the best fitness and diversity of the pool at that generation,
+// returning the generation the run stopped at and why.
+fn run(criteria: &StoppingCriteria, start_generation: usize, pool: impl Fn(usize) -> (f64, f64)) -> (usize, StopReason) {
+    let mut stopping_monitor = StoppingMonitor::new(criteria, start_generation);
+
+    (start_generation..)
+        .find_map(|generation| {
+            let (best_fitness, diversity) = pool(generation);
+            stopping_monitor.check(generation, best_fitness, Some(diversity)).map(|stop_reason| (generation, stop_reason))
+        })
+        .unwrap()
+}
+
+#[test]
+fn generation_limit_counts_from_the_start_generation() {
+    assert_eq!(run(&criteria(), 0, |generation| (generation as f64, 1.0)), (100, StopReason::GenerationLimit));
+    assert_eq!(run(&criteria(), 40, |generation| (generation as f64, 1.0)), (140, StopReason::GenerationLimit));
+}
+
+#[test]
+fn stagnation_counts_generations_since_the_last_improvement() {
+    let criteria = StoppingCriteria { stagnation_generations: Some(10), ..criteria() };
+
+    // Improves until generation 25, then stays level.
+    assert_eq!(run(&criteria, 0, |generation| (generation.min(25) as f64, 1.0)), (35, StopReason::Stagnation));
+    assert_eq!(run(&criteria, 20, |_| (0.5, 1.0)), (30, StopReason::Stagnation));
+}
+
+#[test]
+fn target_fitness_stops_once_reached() {
+    let criteria = StoppingCriteria { target_fitness: Some(0.9), ..criteria() };
+
+    assert_eq!(run(&criteria, 0, |generation| (generation as f64 / 50.0, 1.0)), (45, StopReason::TargetFitness));
+}
+
+#[test]
+fn target_fitness_wins_over_the_generation_limit() {
+    let criteria = StoppingCriteria { target_fitness: Some(0.9), max_generations: 0, ..criteria() };
+
+    assert_eq!(run(&criteria, 0, |_| (1.0, 1.0)), (0, StopReason::TargetFitness));
+}
+
+#[test]
+fn low_diversity_stops_below_the_minimum() {
+    let criteria = StoppingCriteria { minimum_diversity: Some(0.2), ..criteria() };
+
+    // Diversity falls by 0.01 a generation from 0.5.
+    assert_eq!(run(&criteria, 0, |generation| (1.0, (50 - generation as i32) as f64 / 100.0)), (31, StopReason::LowDiversity));
+}
+
+#[test]
+fn time_budget_stops_once_spent() {
+    let spent = StoppingCriteria { time_budget: Some(Duration::ZERO), ..criteria() };
+    let unspent = StoppingCriteria { time_budget: Some(Duration::from_secs(3600)), ..criteria() };
+
+    assert_eq!(run(&spent, 0, |_| (1.0, 1.0)), (0, StopReason::TimeBudget));
+    assert_eq!(run(&unspent, 0, |_| (1.0, 1.0)), (100, StopReason::GenerationLimit));
+}
+
+#[test]
+fn stats_report_the_generation_limit_of_the_last_run() {
+    let config = Config { initial_pool_size: 20, selection_pool_size: 8, offspring_target: 18, stopping: StoppingCriteria { max_generations: 5, ..criteria() }, seed: Some(3), ..Config::default() };
+    let mut genetic_composer = GeneticComposer::new(&melodies::amazing_grace(), &config).unwrap();
+    let limits = |genetic_composer: &GeneticComposer| (genetic_composer.stats().generation, genetic_composer.stats().max_generations);
+
+    assert_eq!(limits(&genetic_composer), (0, 5));
+
+    for expected in [(5, 5), (10, 10)] {
+        genetic_composer.run_until(&config.stopping, |_| Control::Continue, &CancellationToken::new());
+        assert_eq!(limits(&genetic_composer), expected);
+    }
+
+    let mut checkpoint = Vec::new();
+    genetic_composer.write_checkpoint(&mut checkpoint).unwrap();
+    let mut resumed = GeneticComposer::read_checkpoint(checkpoint.as_slice()).unwrap();
+
+    assert_eq!(limits(&resumed), (10, 15));
+
+    resumed.step();
+    resumed.run_until(&StoppingCriteria { max_generations: 2, ..criteria() }, |_| Control::Continue, &CancellationToken::new());
+    assert_eq!(limits(&resumed), (13, 13));
+}
@@ -0,0 +1,64 @@
+use orpheus_core::theory::{Chord, ChordQuality, Interval, Key, Mode, PitchClass, Scale, TheoryError};
+use proptest::prelude::*;
+
+proptest! {
+
+    #[test]
+    fn interval_to_undoes_transposition(pitch_class in 0u16..12, semitones in 0u16..48) {
+        let pitch_class = PitchClass::new(pitch_class).unwrap();
+        let interval = Interval::new(semitones);
+
+        prop_assert_eq!(pitch_class.interval_to(pitch_class + interval), interval);
+        prop_assert_eq!((pitch_class + interval).transpose(interval.invert()), pitch_class);
+        prop_assert!(interval.shortest_semitones() <= 6);
+    }
+
+    #[test]
+    fn chord_index_round_trips(index in 0u16..24) {
+        prop_assert_eq!(Chord::from_index(index).unwrap().index(), index);
+    }
+}
+
+#[test]
+fn interval_arithmetic_folds_into_the_octave() {
+    assert_eq!(Interval::PERFECT_FIFTH + Interval::PERFECT_FOURTH, Interval::UNISON);
+    assert_eq!(Interval::MAJOR_THIRD + Interval::MINOR_THIRD, Interval::PERFECT_FIFTH);
+    assert_eq!(Interval::PERFECT_FIFTH.invert(), Interval::PERFECT_FOURTH);
+    assert_eq!(Interval::new(19), Interval::PERFECT_FIFTH);
+    assert_eq!(Interval::MAJOR_SIXTH.shortest_semitones(), 3);
+}
+
+#[test]
+fn invalid_values_are_rejected() {
+    assert_eq!(PitchClass::new(12), Err(TheoryError::InvalidPitchClass(12)));
+    assert_eq!(ChordQuality::from_index(2), Err(TheoryError::InvalidChordQuality(2)));
+    assert_eq!(Chord::from_index(24), Err(TheoryError::InvalidChord(24)));
+    assert!(serde_json::from_str::<Key>(r#"{"tonic":12,"quality":"Major"}"#).is_err());
+}
+
+#[test]
+fn scales_and_chords_contain_their_notes() {
+    let g = PitchClass::new(7).unwrap();
+    let g_major = Chord::new(g, ChordQuality::Major);
+
+    assert!(g_major.contains(PitchClass::new(11).unwrap()));
+    assert!(!g_major.contains(PitchClass::new(10).unwrap()));
+    assert_eq!(g_major.transpose(Interval::PERFECT_FOURTH), Chord::new(PitchClass::new(0).unwrap(), ChordQuality::Major));
+
+    let g_mixolydian = Scale::new(g, Mode::Mixolydian);
+    assert!(g_mixolydian.contains(PitchClass::new(5).unwrap()));
+    assert!(!g_mixolydian.contains(PitchClass::new(6).unwrap()));
+}
+
+#[test]
+fn keys_list_their_diatonic_chords() {
+    let e_minor = Key::new(PitchClass::new(4).unwrap(), ChordQuality::Minor);
+    let chords: Vec<(u16, ChordQuality)> = e_minor.diatonic_chords().iter().map(|chord| (chord.root.get(), chord.quality)).collect();
+
+    assert_eq!(chords, vec![
+        (4, ChordQuality::Minor), (7, ChordQuality::Major), (9, ChordQuality::Minor),
+        (11, ChordQuality::Major), (0, ChordQuality::Major), (2, ChordQuality::Major),
+    ]);
+    assert_eq!(Key::all().count(), 24);
+    assert_eq!(serde_json::to_string(&e_minor).unwrap(), r#"{"tonic":4,"quality":"Minor"}"#);
+}